@@ -0,0 +1,219 @@
+use crate::Boss;
+use crate::combat::{Attack, Defense, MoveKind};
+use crate::enemy::Enemy;
+use crate::status::StatusEffects;
+
+// Chance of getting away from a regular enemy; bosses never let the player flee
+pub const FLEE_CHANCE: f64 = 0.5;
+// Chance of a boss using its special attack on a turn where it is ready
+pub const BOSS_SPECIAL_CHANCE: f64 = 0.6;
+
+// Chance of an opponent accepting mercy at full health, before any modifier
+const BASE_MERCY_CHANCE: f64 = 0.2;
+// Added in full once the opponent is almost dead
+const WOUNDED_MERCY_BONUS: f64 = 0.5;
+// Per player level above 1, per point of reputation and per point of wisdom
+const LEVEL_MERCY_BONUS: f64 = 0.03;
+const REPUTATION_MERCY_BONUS: f64 = 0.02;
+const WISDOM_MERCY_BONUS: f64 = 0.03;
+const MAX_MERCY_CHANCE: f64 = 0.95;
+
+// Sparing a regular enemy still teaches something, but it drops no gold or loot
+pub const SPARE_XP_FACTOR: f64 = 0.5;
+// Reputation changes when an opponent is spared or a boss is killed
+pub const SPARE_REPUTATION: i32 = 1;
+pub const SPARE_BOSS_REPUTATION: i32 = 3;
+pub const BOSS_KILL_REPUTATION: i32 = -2;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Action {
+    Move(MoveKind),
+    Defend,
+    // Name of the item; its stamina has already been paid
+    UseItem(String),
+    Flee,
+}
+
+// Battles are a queue of turns; one player action queues the player's turn
+// followed by the opponent's reply
+#[derive(Debug, Clone, PartialEq)]
+pub enum Turn {
+    Player(Action),
+    Opponent,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BattleOutcome {
+    Victory,
+    Defeat,
+    Fled,
+    Spared,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MercyOutcome {
+    Accepted,
+    Rejected,
+    // The opponent will not even listen; trying again is pointless
+    Refused,
+}
+
+// What the battle engine needs to know about whoever the player is fighting
+pub trait Combatant {
+    fn name(&self) -> &str;
+    fn health(&self) -> f64;
+    fn max_health(&self) -> f64;
+    fn base_damage(&self) -> f64;
+    fn defense_value(&self) -> f64;
+    fn resistance(&self, kind: MoveKind) -> f64;
+    fn statuses(&self) -> &StatusEffects;
+    fn statuses_mut(&mut self) -> &mut StatusEffects;
+    fn take_damage(&mut self, damage: f64);
+    // None when the opponent refuses any mercy
+    fn mercy_factor(&self) -> Option<f64>;
+
+    fn is_defeated(&self) -> bool {
+        self.health() <= 0.0
+    }
+
+    fn health_fraction(&self) -> f64 {
+        if self.max_health() > 0.0 {
+            (self.health() / self.max_health()).clamp(0.0, 1.0)
+        } else {
+            0.0
+        }
+    }
+
+    fn mercy_chance(&self, player_lvl: f64, reputation: i32, wisdom: f64) -> Option<f64> {
+        self.mercy_factor().map(|factor| {
+            mercy_chance(
+                self.health_fraction(),
+                player_lvl,
+                reputation,
+                wisdom,
+                factor,
+            )
+        })
+    }
+
+    fn attack(&self) -> Attack {
+        Attack::new(self.base_damage() * self.statuses().damage_dealt_factor())
+    }
+
+    // Resistances only apply to the player's moves, not to items
+    fn defense(&self, kind: Option<MoveKind>) -> Defense {
+        let resistance = kind.map_or(1.0, |kind| self.resistance(kind));
+        Defense::new(
+            self.defense_value(),
+            self.statuses().damage_taken_factor() * resistance,
+        )
+    }
+}
+
+impl Combatant for Enemy {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn health(&self) -> f64 {
+        self.health
+    }
+
+    fn max_health(&self) -> f64 {
+        self.max_health
+    }
+
+    fn base_damage(&self) -> f64 {
+        self.damage
+    }
+
+    fn defense_value(&self) -> f64 {
+        self.defense
+    }
+
+    fn resistance(&self, kind: MoveKind) -> f64 {
+        self.definition()
+            .map_or(1.0, |definition| definition.resistance(kind))
+    }
+
+    fn statuses(&self) -> &StatusEffects {
+        &self.statuses
+    }
+
+    fn statuses_mut(&mut self) -> &mut StatusEffects {
+        &mut self.statuses
+    }
+
+    fn take_damage(&mut self, damage: f64) {
+        self.health -= damage;
+    }
+
+    fn mercy_factor(&self) -> Option<f64> {
+        let factor = self
+            .definition()
+            .map_or(1.0, |definition| definition.mercy_factor);
+        (factor > 0.0).then_some(factor)
+    }
+}
+
+impl Combatant for Boss {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn health(&self) -> f64 {
+        self.current_health
+    }
+
+    fn max_health(&self) -> f64 {
+        self.max_health
+    }
+
+    fn base_damage(&self) -> f64 {
+        self.damage
+    }
+
+    fn defense_value(&self) -> f64 {
+        self.defense
+    }
+
+    fn resistance(&self, kind: MoveKind) -> f64 {
+        self.definition()
+            .map_or(1.0, |definition| definition.resistance(kind))
+    }
+
+    fn statuses(&self) -> &StatusEffects {
+        &self.statuses
+    }
+
+    fn statuses_mut(&mut self) -> &mut StatusEffects {
+        &mut self.statuses
+    }
+
+    fn take_damage(&mut self, damage: f64) {
+        self.current_health -= damage;
+    }
+
+    fn mercy_factor(&self) -> Option<f64> {
+        self.definition()
+            .and_then(|definition| definition.mercy.as_ref())
+            .map(|mercy| mercy.factor)
+    }
+}
+
+// Wounded opponents, experienced and wise players and a good reputation all
+// make mercy likelier; `factor` comes from the opponent's definition
+pub fn mercy_chance(
+    health_fraction: f64,
+    player_lvl: f64,
+    reputation: i32,
+    wisdom: f64,
+    factor: f64,
+) -> f64 {
+    let chance = BASE_MERCY_CHANCE
+        + WOUNDED_MERCY_BONUS * (1.0 - health_fraction)
+        + LEVEL_MERCY_BONUS * (player_lvl - 1.0).max(0.0)
+        + REPUTATION_MERCY_BONUS * reputation as f64
+        + WISDOM_MERCY_BONUS * wisdom;
+    (chance * factor).clamp(0.0, MAX_MERCY_CHANCE)
+}
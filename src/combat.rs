@@ -0,0 +1,297 @@
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+// Every hit lands between 90% and 110% of its power
+const VARIANCE: f64 = 0.1;
+// Each strength point above 1 adds 10% to the power of an attack
+const STRENGTH_SCALING: f64 = 0.1;
+pub const BASE_CRIT_CHANCE: f64 = 0.05;
+const CRIT_MULTIPLIER: f64 = 1.5;
+// Defense absorbs part of each hit, but never all of it
+const MIN_DAMAGE: f64 = 1.0;
+// Defending doubles the defense and halves what is left of the hit
+pub const DEFEND_DEFENSE_FACTOR: f64 = 2.0;
+pub const DEFEND_DAMAGE_FACTOR: f64 = 0.5;
+// Stamina recovered at the end of every player turn, and when defending instead
+pub const STAMINA_REGEN: f64 = 5.0;
+pub const DEFEND_STAMINA: f64 = 20.0;
+
+// The player's attacks; enemies and bosses can resist or be weak to each of them
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum MoveKind {
+    #[default]
+    Jab, // Quick and precise, rarely misses
+    Hook,     // Balanced
+    Montante, // Devastating but easy to dodge
+}
+
+impl MoveKind {
+    pub const ALL: [MoveKind; 3] = [MoveKind::Jab, MoveKind::Hook, MoveKind::Montante];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            MoveKind::Jab => "Jab",
+            MoveKind::Hook => "Gancio",
+            MoveKind::Montante => "Montante",
+        }
+    }
+
+    pub fn accuracy(&self) -> f64 {
+        match self {
+            MoveKind::Jab => 0.95,
+            MoveKind::Hook => 0.85,
+            MoveKind::Montante => 0.65,
+        }
+    }
+
+    pub fn damage_multiplier(&self) -> f64 {
+        match self {
+            MoveKind::Jab => 0.8,
+            MoveKind::Hook => 1.2,
+            MoveKind::Montante => 1.8,
+        }
+    }
+
+    pub fn crit_chance(&self) -> f64 {
+        match self {
+            MoveKind::Jab => 0.1,
+            MoveKind::Hook => BASE_CRIT_CHANCE,
+            MoveKind::Montante => BASE_CRIT_CHANCE,
+        }
+    }
+
+    pub fn stamina_cost(&self) -> f64 {
+        match self {
+            MoveKind::Jab => 0.0,
+            MoveKind::Hook => 10.0,
+            MoveKind::Montante => 25.0,
+        }
+    }
+
+    pub fn attack(&self, power: f64) -> Attack {
+        Attack {
+            power: power * self.damage_multiplier(),
+            accuracy: self.accuracy(),
+            crit_chance: self.crit_chance(),
+            ..Attack::new(power)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Attack {
+    pub power: f64,
+    pub strength: f64,
+    pub accuracy: f64,
+    pub crit_chance: f64,
+    pub ignore_defense: bool,
+}
+
+impl Attack {
+    pub fn new(power: f64) -> Self {
+        Self {
+            power,
+            strength: 1.0,
+            accuracy: 1.0,
+            crit_chance: BASE_CRIT_CHANCE,
+            ignore_defense: false,
+        }
+    }
+
+    pub fn with_strength(self, strength: f64) -> Self {
+        Self { strength, ..self }
+    }
+
+    pub fn ignoring_defense(self) -> Self {
+        Self {
+            ignore_defense: true,
+            ..self
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Defense {
+    pub value: f64,
+    // Multiplier from shields, resistances and other effects on the target
+    pub damage_taken_factor: f64,
+}
+
+impl Defense {
+    pub fn new(value: f64, damage_taken_factor: f64) -> Self {
+        Self {
+            value,
+            damage_taken_factor,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hit {
+    pub damage: f64,
+    pub critical: bool,
+    pub missed: bool,
+}
+
+impl Hit {
+    const MISS: Hit = Hit {
+        damage: 0.0,
+        critical: false,
+        missed: true,
+    };
+
+    // Appended to the battle messages
+    pub fn critical_note(&self) -> &'static str {
+        if self.critical { " Colpo critico!" } else { "" }
+    }
+}
+
+// Shared by every fight; the random rolls come from `rng` so results can be
+// reproduced with a seeded generator
+pub fn resolve<R: Rng + ?Sized>(attack: Attack, defense: Defense, rng: &mut R) -> Hit {
+    if !rng.random_bool(attack.accuracy.clamp(0.0, 1.0)) {
+        return Hit::MISS;
+    }
+    let variance = rng.random_range(1.0 - VARIANCE..=1.0 + VARIANCE);
+    let critical = rng.random_bool(attack.crit_chance.clamp(0.0, 1.0));
+    resolve_rolls(attack, defense, variance, critical)
+}
+
+pub fn resolve_rolls(attack: Attack, defense: Defense, variance: f64, critical: bool) -> Hit {
+    let mut damage = attack.power * strength_factor(attack.strength) * variance;
+    if critical {
+        damage *= CRIT_MULTIPLIER;
+    }
+    if !attack.ignore_defense {
+        damage -= defense.value;
+    }
+    Hit {
+        damage: damage.max(MIN_DAMAGE) * defense.damage_taken_factor,
+        critical,
+        missed: false,
+    }
+}
+
+fn strength_factor(strength: f64) -> f64 {
+    (1.0 + STRENGTH_SCALING * (strength - 1.0)).max(0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    const NO_DEFENSE: Defense = Defense {
+        value: 0.0,
+        damage_taken_factor: 1.0,
+    };
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-9,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    #[test]
+    fn defense_is_subtracted_from_the_hit() {
+        let hit = resolve_rolls(Attack::new(10.0), Defense::new(4.0, 1.0), 1.0, false);
+        assert_close(hit.damage, 6.0);
+        assert!(!hit.critical);
+        assert!(!hit.missed);
+    }
+
+    #[test]
+    fn defense_never_absorbs_the_whole_hit() {
+        let hit = resolve_rolls(Attack::new(10.0), Defense::new(50.0, 1.0), 1.0, false);
+        assert_close(hit.damage, MIN_DAMAGE);
+    }
+
+    #[test]
+    fn ignoring_defense_skips_the_subtraction() {
+        let attack = Attack::new(10.0).ignoring_defense();
+        let hit = resolve_rolls(attack, Defense::new(4.0, 1.0), 1.0, false);
+        assert_close(hit.damage, 10.0);
+    }
+
+    #[test]
+    fn critical_hits_are_multiplied_before_defense() {
+        let hit = resolve_rolls(Attack::new(10.0), Defense::new(5.0, 1.0), 1.0, true);
+        assert_close(hit.damage, 10.0 * CRIT_MULTIPLIER - 5.0);
+        assert!(hit.critical);
+    }
+
+    #[test]
+    fn strength_scales_the_power() {
+        let attack = Attack::new(10.0).with_strength(3.0);
+        let hit = resolve_rolls(attack, NO_DEFENSE, 1.0, false);
+        assert_close(hit.damage, 12.0);
+
+        // Strength below 1 weakens the attack, but down to the floor at most
+        let weak = resolve_rolls(
+            Attack::new(10.0).with_strength(-20.0),
+            NO_DEFENSE,
+            1.0,
+            false,
+        );
+        assert_close(weak.damage, MIN_DAMAGE);
+    }
+
+    #[test]
+    fn damage_taken_factor_applies_after_the_floor() {
+        let hit = resolve_rolls(Attack::new(10.0), Defense::new(2.0, 0.5), 1.0, false);
+        assert_close(hit.damage, 4.0);
+
+        let floored = resolve_rolls(Attack::new(10.0), Defense::new(50.0, 2.0), 1.0, false);
+        assert_close(floored.damage, MIN_DAMAGE * 2.0);
+    }
+
+    #[test]
+    fn variance_scales_the_hit() {
+        let hit = resolve_rolls(Attack::new(10.0), NO_DEFENSE, 0.9, false);
+        assert_close(hit.damage, 9.0);
+    }
+
+    #[test]
+    fn attacks_without_accuracy_always_miss() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let attack = Attack {
+            accuracy: 0.0,
+            ..Attack::new(10.0)
+        };
+        for _ in 0..100 {
+            assert_eq!(resolve(attack, NO_DEFENSE, &mut rng), Hit::MISS);
+        }
+    }
+
+    #[test]
+    fn accurate_attacks_land_within_the_variance() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let attack = Attack {
+            crit_chance: 0.0,
+            ..Attack::new(10.0)
+        };
+        for _ in 0..100 {
+            let hit = resolve(attack, NO_DEFENSE, &mut rng);
+            assert!(!hit.missed);
+            assert!(!hit.critical);
+            assert!((9.0..=11.0).contains(&hit.damage), "{}", hit.damage);
+        }
+    }
+
+    #[test]
+    fn seeded_rolls_are_reproducible() {
+        let attack = MoveKind::Montante.attack(10.0);
+        let rolls = |seed| {
+            let mut rng = StdRng::seed_from_u64(seed);
+            (0..20)
+                .map(|_| resolve(attack, NO_DEFENSE, &mut rng))
+                .collect::<Vec<_>>()
+        };
+        let hits = rolls(42);
+        assert_eq!(hits, rolls(42));
+        assert!(hits.iter().any(|hit| hit.missed));
+        assert!(hits.iter().any(|hit| !hit.missed));
+    }
+}
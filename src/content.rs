@@ -0,0 +1,671 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+use std::sync::OnceLock;
+
+use color_eyre::{
+    Result,
+    eyre::{WrapErr, bail},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::combat::MoveKind;
+use crate::status::StatusKind;
+use crate::{BossType, Places};
+
+pub const BOSSES_PATH: &str = "content/bosses.json";
+pub const ITEMS_PATH: &str = "content/items.json";
+pub const SHOPS_PATH: &str = "content/shops.json";
+pub const ENEMIES_PATH: &str = "content/enemies.json";
+pub const ENCOUNTERS_PATH: &str = "content/encounters.json";
+pub const PORTRAITS_PATH: &str = "content/portraits.json";
+
+// Shipped data set, used when no content file is found next to the game
+const DEFAULT_BOSSES: &str = include_str!("../content/bosses.json");
+const DEFAULT_ITEMS: &str = include_str!("../content/items.json");
+const DEFAULT_SHOPS: &str = include_str!("../content/shops.json");
+const DEFAULT_ENEMIES: &str = include_str!("../content/enemies.json");
+const DEFAULT_ENCOUNTERS: &str = include_str!("../content/encounters.json");
+const DEFAULT_PORTRAITS: &str = include_str!("../content/portraits.json");
+
+static BOSSES: OnceLock<Vec<BossDefinition>> = OnceLock::new();
+static ITEMS: OnceLock<Vec<ItemDefinition>> = OnceLock::new();
+static SHOPS: OnceLock<Vec<ShopDefinition>> = OnceLock::new();
+static ENEMIES: OnceLock<Vec<EnemyDefinition>> = OnceLock::new();
+static ENCOUNTERS: OnceLock<EncounterConfig> = OnceLock::new();
+static PORTRAITS: OnceLock<Vec<PortraitDefinition>> = OnceLock::new();
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BossDefinition {
+    pub id: String,
+    // Identifies the bosses the story refers to; data-only bosses use Custom
+    #[serde(default = "custom_boss_type")]
+    pub boss_type: BossType,
+    pub name: String,
+    #[serde(default)]
+    pub place: Option<Places>,
+    pub max_health: f64,
+    pub damage: f64,
+    pub defense: f64,
+    pub special_attack_cooldown: u32,
+    #[serde(default)]
+    pub special: SpecialDefinition,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub special_ability: String,
+    #[serde(default)]
+    pub dialogue: Vec<String>,
+    // Spoken once when health first drops to low_health_threshold percent
+    #[serde(default)]
+    pub low_health_dialogue: Vec<String>,
+    #[serde(default = "default_low_health_threshold")]
+    pub low_health_threshold: f64,
+    // Last words, spoken before the victory rewards
+    #[serde(default)]
+    pub defeat_dialogue: Vec<String>,
+    // phases[i] is entered from phase i + 1 once health drops to its threshold
+    #[serde(default)]
+    pub phases: Vec<PhaseDefinition>,
+    #[serde(default)]
+    pub xp_reward: f64,
+    #[serde(default)]
+    pub reward_item: Option<String>,
+    #[serde(default)]
+    pub victory_message: String,
+    // Damage multiplier per move: below 1 resists it, above 1 is weak to it
+    #[serde(default)]
+    pub resistances: HashMap<MoveKind, f64>,
+    // Bosses without it refuse any mercy
+    #[serde(default)]
+    pub mercy: Option<MercyDefinition>,
+}
+
+impl BossDefinition {
+    pub fn resistance(&self, kind: MoveKind) -> f64 {
+        self.resistances.get(&kind).copied().unwrap_or(1.0)
+    }
+
+    // Bosses without a special name fall back to their ability description
+    pub fn special_name(&self) -> &str {
+        if self.special.name.is_empty() {
+            &self.special_ability
+        } else {
+            &self.special.name
+        }
+    }
+}
+
+// The boss special attack, used whenever its cooldown allows
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpecialDefinition {
+    #[serde(default)]
+    pub name: String,
+    // Multiplies the boss damage; 0 makes the special a support move with no hit
+    #[serde(default = "one")]
+    pub damage_multiplier: f64,
+    #[serde(default)]
+    pub ignore_defense: bool,
+    // Health the boss recovers, up to its max
+    #[serde(default)]
+    pub heal: f64,
+    #[serde(default)]
+    pub effect: Option<SpecialEffect>,
+    // Battle message; {boss} and {damage} are filled in
+    #[serde(default)]
+    pub message: String,
+}
+
+impl Default for SpecialDefinition {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            damage_multiplier: 1.0,
+            ignore_defense: false,
+            heal: 0.0,
+            effect: None,
+            message: String::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct SpecialEffect {
+    pub kind: StatusKind,
+    #[serde(default)]
+    pub target: SpecialTarget,
+    pub magnitude: f64,
+    pub turns: u32,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SpecialTarget {
+    #[default]
+    Boss,
+    Player,
+}
+
+// What sparing a boss gives, in place of the victory rewards
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MercyDefinition {
+    // Multiplies the chance of the boss accepting mercy
+    #[serde(default = "one")]
+    pub factor: f64,
+    #[serde(default)]
+    pub xp_reward: f64,
+    #[serde(default)]
+    pub reward_item: Option<String>,
+    #[serde(default)]
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PhaseDefinition {
+    // Percentage of max health at or below which the phase starts
+    pub health_threshold: f64,
+    #[serde(default = "one")]
+    pub damage_multiplier: f64,
+    #[serde(default = "one")]
+    pub defense_multiplier: f64,
+    #[serde(default)]
+    pub heal: f64,
+    #[serde(default)]
+    pub special_attack_cooldown: Option<u32>,
+    // Spoken by the boss as the phase starts
+    #[serde(default)]
+    pub dialogue: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ItemDefinition {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub usable: bool,
+    #[serde(default)]
+    pub value: u32,
+    #[serde(default)]
+    pub effect: Option<ItemEffect>,
+    // Non-consumable items (the boss trophies) stay in the bag after use
+    #[serde(default = "yes")]
+    pub consumable: bool,
+    // Where the item is made; it trades for less there
+    #[serde(default)]
+    pub origin: Option<Places>,
+    // Stamina spent when the item is used in battle
+    #[serde(default)]
+    pub stamina_cost: f64,
+    // Items that can be worn in one of the equipment slots
+    #[serde(default)]
+    pub equipment: Option<EquipmentDefinition>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EquipSlot {
+    Weapon,
+    Armor,
+    Relic,
+}
+
+impl EquipSlot {
+    pub const ALL: [EquipSlot; 3] = [EquipSlot::Weapon, EquipSlot::Armor, EquipSlot::Relic];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            EquipSlot::Weapon => "Arma",
+            EquipSlot::Armor => "Armatura",
+            EquipSlot::Relic => "Reliquia",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct EquipmentDefinition {
+    pub slot: EquipSlot,
+    #[serde(flatten)]
+    pub bonus: EquipmentBonus,
+}
+
+// Added to the player's stats while the item is worn
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EquipmentBonus {
+    pub damage: f64,
+    pub defense: f64,
+    pub max_health: f64,
+    pub heal_factor: f64,
+    pub wisdom: f64,
+}
+
+impl EquipmentBonus {
+    pub fn plus(self, other: EquipmentBonus) -> Self {
+        Self {
+            damage: self.damage + other.damage,
+            defense: self.defense + other.defense,
+            max_health: self.max_health + other.max_health,
+            heal_factor: self.heal_factor + other.heal_factor,
+            wisdom: self.wisdom + other.wisdom,
+        }
+    }
+
+    // Short description for the inventory, e.g. "ATK +5, DEF +2"
+    pub fn describe(&self) -> String {
+        let mut parts = vec![];
+        if self.damage != 0.0 {
+            parts.push(format!("ATK {:+.0}", self.damage));
+        }
+        if self.defense != 0.0 {
+            parts.push(format!("DEF {:+.0}", self.defense));
+        }
+        if self.max_health != 0.0 {
+            parts.push(format!("HP {:+.0}", self.max_health));
+        }
+        if self.heal_factor != 0.0 {
+            parts.push(format!("Cura {:+.1}", self.heal_factor));
+        }
+        if self.wisdom != 0.0 {
+            parts.push(format!("Saggezza {:+.0}", self.wisdom));
+        }
+        parts.join(", ")
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(tag = "kind")]
+pub enum ItemEffect {
+    Heal { amount: f64 },
+    DamageBuff { amount: f64, turns: u32 },
+    Damage { amount: f64 },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShopDefinition {
+    pub place: Places,
+    #[serde(default = "default_restock_after_battles")]
+    pub restock_after_battles: u32,
+    pub items: Vec<ShopEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShopEntry {
+    pub item: String,
+    // Base price, before location and market adjustments
+    pub price: u32,
+    // Units on the shelf after a restock
+    #[serde(default = "default_stock")]
+    pub stock: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnemyDefinition {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    // Regions where the enemy roams
+    pub places: Vec<Places>,
+    pub max_health: f64,
+    pub damage: f64,
+    #[serde(default)]
+    pub defense: f64,
+    #[serde(default)]
+    pub xp_reward: f64,
+    #[serde(default)]
+    pub gold_reward: u32,
+    // Multiplies the chance of the enemy accepting mercy; 0 never does
+    #[serde(default = "one")]
+    pub mercy_factor: f64,
+    #[serde(default)]
+    pub loot: Vec<LootEntry>,
+    #[serde(default)]
+    pub resistances: HashMap<MoveKind, f64>,
+}
+
+impl EnemyDefinition {
+    pub fn resistance(&self, kind: MoveKind) -> f64 {
+        self.resistances.get(&kind).copied().unwrap_or(1.0)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LootEntry {
+    pub item: String,
+    // Probability between 0 and 1 that the item drops
+    pub chance: f64,
+}
+
+// Chance of a random encounter each time the player travels or explores
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EncounterConfig {
+    // Used for places without their own rate
+    pub base_rate: f64,
+    // Added to the rate for every level above the first
+    #[serde(default)]
+    pub level_modifier: f64,
+    #[serde(default)]
+    pub min_rate: f64,
+    #[serde(default = "one")]
+    pub max_rate: f64,
+    #[serde(default)]
+    pub places: HashMap<Places, f64>,
+}
+
+// ASCII art shown next to a boss; phases[i] is drawn in phase i + 1 and the
+// last one is kept for any later phase
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortraitDefinition {
+    pub boss: String,
+    pub phases: Vec<Vec<String>>,
+}
+
+impl PortraitDefinition {
+    pub fn for_phase(&self, phase: u32) -> &[String] {
+        let index = (phase.max(1) as usize - 1).min(self.phases.len() - 1);
+        &self.phases[index]
+    }
+
+    pub fn width(&self) -> usize {
+        self.phases
+            .iter()
+            .flatten()
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or_default()
+    }
+}
+
+impl EncounterConfig {
+    pub fn rate(&self, place: Places, level: f64) -> f64 {
+        let place_rate = self.places.get(&place).copied().unwrap_or(self.base_rate);
+        (place_rate + self.level_modifier * (level - 1.0)).clamp(self.min_rate, self.max_rate)
+    }
+}
+
+fn one() -> f64 {
+    1.0
+}
+
+fn default_low_health_threshold() -> f64 {
+    20.0
+}
+
+fn yes() -> bool {
+    true
+}
+
+fn default_restock_after_battles() -> u32 {
+    3
+}
+
+fn default_stock() -> u32 {
+    5
+}
+
+fn custom_boss_type() -> BossType {
+    BossType::Custom
+}
+
+// Loads the content files if present, falling back to the shipped data set.
+// Must be called before the first lookup to take effect.
+pub fn load() -> Result<()> {
+    let bosses = load_file(BOSSES_PATH, DEFAULT_BOSSES, parse_bosses)?;
+    let items = load_file(ITEMS_PATH, DEFAULT_ITEMS, parse_items)?;
+    let shops = load_file(SHOPS_PATH, DEFAULT_SHOPS, parse_shops)?;
+    check_shop_items(&shops, &items)?;
+    let enemies = load_file(ENEMIES_PATH, DEFAULT_ENEMIES, parse_enemies)?;
+    check_loot_items(&enemies, &items)?;
+    let encounters = load_file(ENCOUNTERS_PATH, DEFAULT_ENCOUNTERS, parse_encounters)?;
+    let portraits = load_file(PORTRAITS_PATH, DEFAULT_PORTRAITS, parse_portraits)?;
+    check_portrait_bosses(&portraits, &bosses)?;
+    // Ignored if the defaults were already loaded lazily
+    let _ = BOSSES.set(bosses);
+    let _ = ITEMS.set(items);
+    let _ = SHOPS.set(shops);
+    let _ = ENEMIES.set(enemies);
+    let _ = ENCOUNTERS.set(encounters);
+    let _ = PORTRAITS.set(portraits);
+    Ok(())
+}
+
+fn load_file<T>(path: &str, default: &str, parse: fn(&str) -> Result<T>) -> Result<T> {
+    if Path::new(path).exists() {
+        let json =
+            fs::read_to_string(path).wrap_err_with(|| format!("impossibile leggere {}", path))?;
+        parse(&json).wrap_err_with(|| format!("contenuto non valido in {}", path))
+    } else {
+        parse(default)
+    }
+}
+
+fn parse_bosses(json: &str) -> Result<Vec<BossDefinition>> {
+    let bosses: Vec<BossDefinition> = serde_json::from_str(json)?;
+    let mut ids = HashSet::new();
+    for boss in &bosses {
+        if boss.id.is_empty() {
+            bail!("boss \"{}\" senza id", boss.name);
+        }
+        if !ids.insert(boss.id.as_str()) {
+            bail!("id boss duplicato: {}", boss.id);
+        }
+        if boss.max_health <= 0.0 {
+            bail!("il boss {} deve avere max_health positivo", boss.id);
+        }
+        if boss.boss_type == BossType::None {
+            bail!("il boss {} non può avere boss_type None", boss.id);
+        }
+        check_resistances(&boss.id, &boss.resistances)?;
+        if boss.mercy.as_ref().is_some_and(|mercy| mercy.factor < 0.0) {
+            bail!("fattore di pietà negativo per {}", boss.id);
+        }
+        check_special(&boss.id, &boss.special)?;
+    }
+    Ok(bosses)
+}
+
+fn check_special(id: &str, special: &SpecialDefinition) -> Result<()> {
+    if special.damage_multiplier < 0.0 || special.heal < 0.0 {
+        bail!("attacco speciale di {} con valori negativi", id);
+    }
+    if special
+        .effect
+        .is_some_and(|effect| effect.magnitude < 0.0 || effect.turns == 0)
+    {
+        bail!("effetto dell'attacco speciale di {} non valido", id);
+    }
+    Ok(())
+}
+
+fn check_resistances(id: &str, resistances: &HashMap<MoveKind, f64>) -> Result<()> {
+    if let Some((kind, _)) = resistances.iter().find(|(_, factor)| **factor < 0.0) {
+        bail!("resistenza negativa a {} per {}", kind.name(), id);
+    }
+    Ok(())
+}
+
+fn parse_items(json: &str) -> Result<Vec<ItemDefinition>> {
+    let items: Vec<ItemDefinition> = serde_json::from_str(json)?;
+    let mut names = HashSet::new();
+    for item in &items {
+        if !names.insert(item.name.as_str()) {
+            bail!("oggetto duplicato: {}", item.name);
+        }
+        if item.usable && item.effect.is_none() {
+            bail!("l'oggetto {} è utilizzabile ma non ha effetto", item.name);
+        }
+        if item.equipment.is_some() && item.consumable && item.usable {
+            bail!(
+                "l'oggetto {} non può essere sia consumabile che equipaggiabile",
+                item.name
+            );
+        }
+    }
+    Ok(items)
+}
+
+fn parse_shops(json: &str) -> Result<Vec<ShopDefinition>> {
+    let shops: Vec<ShopDefinition> = serde_json::from_str(json)?;
+    let mut places = HashSet::new();
+    for shop in &shops {
+        if !places.insert(shop.place) {
+            bail!("più negozi per il luogo {}", shop.place.name());
+        }
+    }
+    Ok(shops)
+}
+
+// Shops may only sell items described in the item catalog
+fn check_shop_items(shops: &[ShopDefinition], items: &[ItemDefinition]) -> Result<()> {
+    for shop in shops {
+        for entry in &shop.items {
+            if !items.iter().any(|item| item.name == entry.item) {
+                bail!(
+                    "il negozio di {} vende un oggetto sconosciuto: {}",
+                    shop.place.name(),
+                    entry.item
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+fn parse_enemies(json: &str) -> Result<Vec<EnemyDefinition>> {
+    let enemies: Vec<EnemyDefinition> = serde_json::from_str(json)?;
+    let mut ids = HashSet::new();
+    for enemy in &enemies {
+        if !ids.insert(enemy.id.as_str()) {
+            bail!("id nemico duplicato: {}", enemy.id);
+        }
+        if enemy.max_health <= 0.0 {
+            bail!("il nemico {} deve avere max_health positivo", enemy.id);
+        }
+        if let Some(loot) = enemy
+            .loot
+            .iter()
+            .find(|loot| !(0.0..=1.0).contains(&loot.chance))
+        {
+            bail!(
+                "probabilità non valida per {} nel bottino di {}",
+                loot.item,
+                enemy.id
+            );
+        }
+        check_resistances(&enemy.id, &enemy.resistances)?;
+        if enemy.mercy_factor < 0.0 {
+            bail!("fattore di pietà negativo per {}", enemy.id);
+        }
+    }
+    if enemies.is_empty() {
+        bail!("serve almeno un nemico");
+    }
+    Ok(enemies)
+}
+
+fn check_loot_items(enemies: &[EnemyDefinition], items: &[ItemDefinition]) -> Result<()> {
+    for enemy in enemies {
+        for loot in &enemy.loot {
+            if !items.iter().any(|item| item.name == loot.item) {
+                bail!(
+                    "il nemico {} lascia un oggetto sconosciuto: {}",
+                    enemy.id,
+                    loot.item
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+fn parse_encounters(json: &str) -> Result<EncounterConfig> {
+    let config: EncounterConfig = serde_json::from_str(json)?;
+    let valid = |rate: f64| (0.0..=1.0).contains(&rate);
+    if !valid(config.min_rate) || !valid(config.max_rate) || config.min_rate > config.max_rate {
+        bail!("min_rate e max_rate devono essere tra 0 e 1, con min_rate <= max_rate");
+    }
+    Ok(config)
+}
+
+fn parse_portraits(json: &str) -> Result<Vec<PortraitDefinition>> {
+    let portraits: Vec<PortraitDefinition> = serde_json::from_str(json)?;
+    let mut bosses = HashSet::new();
+    for portrait in &portraits {
+        if !bosses.insert(portrait.boss.as_str()) {
+            bail!("ritratto duplicato per il boss {}", portrait.boss);
+        }
+        if portrait.phases.is_empty() {
+            bail!("il ritratto di {} non ha fasi", portrait.boss);
+        }
+    }
+    Ok(portraits)
+}
+
+fn check_portrait_bosses(
+    portraits: &[PortraitDefinition],
+    bosses: &[BossDefinition],
+) -> Result<()> {
+    for portrait in portraits {
+        if !bosses.iter().any(|boss| boss.id == portrait.boss) {
+            bail!("ritratto per un boss sconosciuto: {}", portrait.boss);
+        }
+    }
+    Ok(())
+}
+
+pub fn bosses() -> &'static [BossDefinition] {
+    BOSSES.get_or_init(|| parse_bosses(DEFAULT_BOSSES).expect("shipped boss data is valid"))
+}
+
+pub fn boss_by_id(id: &str) -> Option<&'static BossDefinition> {
+    bosses().iter().find(|boss| boss.id == id)
+}
+
+pub fn boss_by_type(boss_type: BossType) -> Option<&'static BossDefinition> {
+    bosses().iter().find(|boss| boss.boss_type == boss_type)
+}
+
+pub fn boss_for_place(place: Places) -> Option<&'static BossDefinition> {
+    bosses().iter().find(|boss| boss.place == Some(place))
+}
+
+pub fn items() -> &'static [ItemDefinition] {
+    ITEMS.get_or_init(|| parse_items(DEFAULT_ITEMS).expect("shipped item data is valid"))
+}
+
+pub fn item_by_name(name: &str) -> Option<&'static ItemDefinition> {
+    items().iter().find(|item| item.name == name)
+}
+
+pub fn shops() -> &'static [ShopDefinition] {
+    SHOPS.get_or_init(|| parse_shops(DEFAULT_SHOPS).expect("shipped shop data is valid"))
+}
+
+pub fn shop_for_place(place: Places) -> Option<&'static ShopDefinition> {
+    shops().iter().find(|shop| shop.place == place)
+}
+
+pub fn enemies() -> &'static [EnemyDefinition] {
+    ENEMIES.get_or_init(|| parse_enemies(DEFAULT_ENEMIES).expect("shipped enemy data is valid"))
+}
+
+pub fn enemies_for_place(place: Places) -> Vec<&'static EnemyDefinition> {
+    enemies()
+        .iter()
+        .filter(|enemy| enemy.places.contains(&place))
+        .collect()
+}
+
+pub fn encounters() -> &'static EncounterConfig {
+    ENCOUNTERS.get_or_init(|| {
+        parse_encounters(DEFAULT_ENCOUNTERS).expect("shipped encounter data is valid")
+    })
+}
+
+pub fn portraits() -> &'static [PortraitDefinition] {
+    PORTRAITS
+        .get_or_init(|| parse_portraits(DEFAULT_PORTRAITS).expect("shipped portrait data is valid"))
+}
+
+pub fn portrait_for_boss(id: &str) -> Option<&'static PortraitDefinition> {
+    portraits().iter().find(|portrait| portrait.boss == id)
+}
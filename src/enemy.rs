@@ -0,0 +1,70 @@
+use rand::Rng;
+use rand::seq::IndexedRandom;
+
+use crate::Places;
+use crate::content::{self, EnemyDefinition};
+use crate::status::StatusEffects;
+
+#[derive(Debug, Clone, Default)]
+pub struct Enemy {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub max_health: f64,
+    pub health: f64,
+    pub damage: f64,
+    pub defense: f64,
+    pub xp_reward: f64,
+    pub gold_reward: u32,
+    pub statuses: StatusEffects,
+}
+
+impl Enemy {
+    pub fn from_definition(definition: &EnemyDefinition) -> Self {
+        Self {
+            id: definition.id.clone(),
+            name: definition.name.clone(),
+            description: definition.description.clone(),
+            max_health: definition.max_health,
+            health: definition.max_health,
+            damage: definition.damage,
+            defense: definition.defense,
+            xp_reward: definition.xp_reward,
+            gold_reward: definition.gold_reward,
+            statuses: StatusEffects::default(),
+        }
+    }
+
+    // Picks a random enemy from the bestiary of the place; places without
+    // enemies of their own fall back to the whole roster
+    pub fn random_for_place(place: Places) -> Self {
+        let mut rng = rand::rng();
+        let local = content::enemies_for_place(place);
+        let definition = match local.choose(&mut rng) {
+            Some(definition) => *definition,
+            None => content::enemies()
+                .choose(&mut rng)
+                .expect("the bestiary is never empty"),
+        };
+        Self::from_definition(definition)
+    }
+
+    pub fn definition(&self) -> Option<&'static EnemyDefinition> {
+        content::enemies().iter().find(|enemy| enemy.id == self.id)
+    }
+
+    // Rolls every entry of the loot table independently
+    pub fn roll_loot(&self) -> Vec<String> {
+        let mut rng = rand::rng();
+        self.definition()
+            .map(|definition| {
+                definition
+                    .loot
+                    .iter()
+                    .filter(|loot| rng.random_bool(loot.chance))
+                    .map(|loot| loot.item.clone())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
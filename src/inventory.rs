@@ -0,0 +1,122 @@
+use serde::{Deserialize, Serialize};
+
+use crate::content::{self, EquipSlot, EquipmentBonus};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InventoryItem {
+    pub name: String,
+    pub quantity: u32,
+    pub description: String,
+    pub usable: bool,
+    pub value: u32,
+}
+
+impl InventoryItem {
+    // Description, usability and value come from content/items.json
+    pub fn new(name: &str, quantity: u32) -> Self {
+        let definition = content::item_by_name(name);
+        Self {
+            name: name.to_string(),
+            quantity,
+            description: definition
+                .map(|item| item.description.clone())
+                .unwrap_or_default(),
+            usable: definition.is_some_and(|item| item.usable),
+            value: definition.map_or(0, |item| item.value),
+        }
+    }
+}
+
+// Items of the same name are stacked into a single entry
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Inventory {
+    pub items: Vec<InventoryItem>,
+}
+
+impl Inventory {
+    pub fn add(&mut self, name: &str, quantity: u32) {
+        match self.items.iter_mut().find(|item| item.name == name) {
+            Some(item) => item.quantity += quantity,
+            None => self.items.push(InventoryItem::new(name, quantity)),
+        }
+    }
+
+    // Takes one unit out of the stack at `index`, dropping the stack when it empties
+    pub fn take_one(&mut self, index: usize) -> Option<InventoryItem> {
+        let item = self.items.get_mut(index)?;
+        item.quantity -= 1;
+        let taken = InventoryItem {
+            quantity: 1,
+            ..item.clone()
+        };
+        if item.quantity == 0 {
+            self.items.remove(index);
+        }
+        Some(taken)
+    }
+
+    pub fn get(&self, index: usize) -> Option<&InventoryItem> {
+        self.items.get(index)
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
+// Items currently worn; they are kept out of the bag until taken off
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Equipment {
+    pub weapon: Option<String>,
+    pub armor: Option<String>,
+    pub relic: Option<String>,
+}
+
+impl Equipment {
+    pub fn get(&self, slot: EquipSlot) -> Option<&str> {
+        self.slot(slot).as_deref()
+    }
+
+    // Puts `name` in the slot and returns whatever was worn there before
+    pub fn equip(&mut self, slot: EquipSlot, name: &str) -> Option<String> {
+        self.slot_mut(slot).replace(name.to_string())
+    }
+
+    pub fn unequip(&mut self, slot: EquipSlot) -> Option<String> {
+        self.slot_mut(slot).take()
+    }
+
+    // Sum of the bonuses of everything worn
+    pub fn bonus(&self) -> EquipmentBonus {
+        EquipSlot::ALL
+            .iter()
+            .filter_map(|slot| self.get(*slot))
+            .filter_map(content::item_by_name)
+            .filter_map(|item| item.equipment)
+            .fold(EquipmentBonus::default(), |total, equipment| {
+                total.plus(equipment.bonus)
+            })
+    }
+
+    fn slot(&self, slot: EquipSlot) -> &Option<String> {
+        match slot {
+            EquipSlot::Weapon => &self.weapon,
+            EquipSlot::Armor => &self.armor,
+            EquipSlot::Relic => &self.relic,
+        }
+    }
+
+    fn slot_mut(&mut self, slot: EquipSlot) -> &mut Option<String> {
+        match slot {
+            EquipSlot::Weapon => &mut self.weapon,
+            EquipSlot::Armor => &mut self.armor,
+            EquipSlot::Relic => &mut self.relic,
+        }
+    }
+}
@@ -13,43 +13,57 @@ use ratatui::{
     style::Stylize,
     symbols::{border::FULL, scrollbar::VERTICAL},
     text::Line,
-    widgets::{Block, BorderType, Borders, Padding, Paragraph, Wrap},
+    widgets::{
+        Block, BorderType, Borders, Gauge, LineGauge, Padding, Paragraph, Wrap,
+        canvas::{Canvas, Line as CanvasLine, Map, MapResolution},
+    },
 };
 #[allow(unused)]
 use ratatui::{style::palette::material::GRAY, symbols::border};
-#[allow(unused)]
 use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
 #[allow(unused)]
 use std::fs;
 #[allow(unused)]
 use std::io;
+use std::path::PathBuf;
 #[allow(unused)]
 use std::time::{Duration, Instant};
-use std::{cmp::Ordering, default};
+
+mod battle;
+mod combat;
+mod content;
+mod enemy;
+mod inventory;
+mod map;
+mod progression;
+mod save;
+mod shop;
+mod status;
+
+use battle::{Action, BattleOutcome, Combatant, MercyOutcome, Turn};
+use combat::{Attack, Defense, Hit, MoveKind};
+use content::{BossDefinition, EquipSlot, ItemEffect, ShopEntry, SpecialTarget};
+use enemy::Enemy;
+use inventory::{Equipment, Inventory};
+use progression::StatKind;
+use shop::Market;
+use status::{StatusEffect, StatusEffects, StatusKind};
 
 fn main() -> color_eyre::Result<()> {
     color_eyre::install()?;
+    content::load()?;
     let terminal = ratatui::init();
     let result = App::new().run(terminal);
     ratatui::restore();
     result
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct InventoryItem {
-    pub name: String,
-    pub quantity: u32,
-    pub description: String,
-    pub usable: bool,
-    pub value: u32,
-}
-
 #[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
 pub enum GameState {
     #[default]
     MainMenu,
     Story,
-    Fight,
     Minigame,
     GameOver,
     Heal,
@@ -58,9 +72,23 @@ pub enum GameState {
     Test,
     Mercy,
     Battle,
+    SaveSlots,
+    ShopBuy,
+    ShopSell,
+    Travel,
+    BossEncounter,
+    Epilogue,
+    StatPoints,
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum SaveMenuMode {
+    #[default]
+    Load,
+    Save,
 }
 
-#[derive(Debug, Default, Clone, PartialEq, Eq, Copy)]
+#[derive(Debug, Default, Clone, PartialEq, Eq, Copy, Serialize, Deserialize)]
 pub enum BossType {
     #[default]
     None,
@@ -68,10 +96,12 @@ pub enum BossType {
     TyrantOfTyre,   // Mid game boss
     BabylonianSage, // Late game boss
     FinalBoss,      // End game boss
+    Custom,         // Boss defined only in the content files
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Boss {
+    pub id: String,
     pub boss_type: BossType,
     pub name: String,
     pub max_health: f64,
@@ -84,87 +114,70 @@ pub struct Boss {
     pub defeated: bool,
     pub description: String,
     pub special_ability: String,
+    // Effects only last for the current battle and are not saved
+    #[serde(skip)]
+    pub statuses: StatusEffects,
+    #[serde(skip)]
+    pub low_health_spoken: bool,
 }
 
 impl Boss {
     pub fn new(boss_type: BossType) -> Self {
-        match boss_type {
-            BossType::SamosGuardian => Boss {
-                boss_type: BossType::SamosGuardian,
-                name: "Guardiano di Samos".to_string(),
-                max_health: 200.0,
-                current_health: 200.0,
-                damage: 25.0,
-                defense: 10.0,
-                special_attack_cooldown: 3,
-                current_cooldown: 0,
-                phase: 1,
-                defeated: false,
-                description: "Un antico guardiano che protegge i segreti di Samos".to_string(),
-                special_ability: "Scudo Geometrico - Riduce il danno del 50% per 2 turni"
-                    .to_string(),
-            },
-            BossType::TyrantOfTyre => Boss {
-                boss_type: BossType::TyrantOfTyre,
-                name: "Tiranno di Tiro".to_string(),
-                max_health: 350.0,
-                current_health: 350.0,
-                damage: 35.0,
-                defense: 15.0,
-                special_attack_cooldown: 4,
-                current_cooldown: 0,
-                phase: 1,
-                defeated: false,
-                description: "Un tiranno crudele che governa Tiro con pugno di ferro".to_string(),
-                special_ability: "Ira del Tiranno - Attacco devastante che ignora la difesa"
-                    .to_string(),
-            },
-            BossType::BabylonianSage => Boss {
-                boss_type: BossType::BabylonianSage,
-                name: "Saggio Babilonese".to_string(),
-                max_health: 500.0,
-                current_health: 500.0,
-                damage: 45.0,
-                defense: 20.0,
-                special_attack_cooldown: 5,
-                current_cooldown: 0,
-                phase: 1,
-                defeated: false,
-                description: "Un antico saggio che custodisce i misteri della matematica"
-                    .to_string(),
-                special_ability: "Teorema Antico - Si cura e potenzia i suoi attacchi".to_string(),
-            },
-            BossType::FinalBoss => Boss {
-                boss_type: BossType::FinalBoss,
-                name: "Ombra del Caos".to_string(),
-                max_health: 750.0,
-                current_health: 750.0,
-                damage: 60.0,
-                defense: 25.0,
-                special_attack_cooldown: 3,
-                current_cooldown: 0,
-                phase: 1,
-                defeated: false,
-                description: "L'antitesi di tutto ciò che Pitagora rappresenta".to_string(),
-                special_ability: "Caos Numerico - Confonde e danneggia gravemente".to_string(),
-            },
-            BossType::None => Boss {
-                boss_type: BossType::None,
-                name: "Nessun Boss".to_string(),
-                max_health: 0.0,
-                current_health: 0.0,
-                damage: 0.0,
-                defense: 0.0,
-                special_attack_cooldown: 0,
-                current_cooldown: 0,
-                phase: 1,
-                defeated: true,
-                description: "".to_string(),
-                special_ability: "".to_string(),
-            },
+        content::boss_by_type(boss_type)
+            .map(Boss::from_definition)
+            .unwrap_or_else(Boss::none)
+    }
+
+    pub fn from_id(id: &str) -> Self {
+        content::boss_by_id(id)
+            .map(Boss::from_definition)
+            .unwrap_or_else(Boss::none)
+    }
+
+    pub fn from_definition(definition: &BossDefinition) -> Self {
+        Boss {
+            id: definition.id.clone(),
+            boss_type: definition.boss_type,
+            name: definition.name.clone(),
+            max_health: definition.max_health,
+            current_health: definition.max_health,
+            damage: definition.damage,
+            defense: definition.defense,
+            special_attack_cooldown: definition.special_attack_cooldown,
+            current_cooldown: 0,
+            phase: 1,
+            defeated: false,
+            description: definition.description.clone(),
+            special_ability: definition.special_ability.clone(),
+            statuses: StatusEffects::default(),
+            low_health_spoken: false,
+        }
+    }
+
+    fn none() -> Self {
+        Boss {
+            id: String::new(),
+            boss_type: BossType::None,
+            name: "Nessun Boss".to_string(),
+            max_health: 0.0,
+            current_health: 0.0,
+            damage: 0.0,
+            defense: 0.0,
+            special_attack_cooldown: 0,
+            current_cooldown: 0,
+            phase: 1,
+            defeated: true,
+            description: "".to_string(),
+            special_ability: "".to_string(),
+            statuses: StatusEffects::default(),
+            low_health_spoken: false,
         }
     }
 
+    pub fn definition(&self) -> Option<&'static BossDefinition> {
+        content::boss_by_id(&self.id)
+    }
+
     pub fn get_health_percentage(&self) -> f64 {
         (self.current_health / self.max_health) * 100.0
     }
@@ -185,50 +198,25 @@ impl Boss {
 
     pub fn enter_next_phase(&mut self) {
         self.phase += 1;
-        match self.boss_type {
-            BossType::SamosGuardian => {
-                if self.phase == 2 {
-                    self.damage *= 1.2;
-                    self.special_attack_cooldown = 2; // More frequent specials
-                }
-            }
-            BossType::TyrantOfTyre => {
-                if self.phase == 2 {
-                    self.damage *= 1.3;
-                    self.defense *= 0.8; // Less defense but more damage
-                }
-            }
-            BossType::BabylonianSage => {
-                if self.phase == 2 {
-                    self.current_health += 100.0; // Heals when entering phase 2
-                    self.damage *= 1.4;
-                }
-            }
-            BossType::FinalBoss => {
-                if self.phase == 2 {
-                    self.damage *= 1.5;
-                    self.special_attack_cooldown = 2;
-                } else if self.phase == 3 {
-                    self.damage *= 1.8;
-                    self.special_attack_cooldown = 1; // Very frequent specials
-                }
-            }
-            BossType::None => {}
+        let Some(phase) = self
+            .definition()
+            .and_then(|definition| definition.phases.get(self.phase as usize - 2))
+        else {
+            return;
+        };
+        self.damage *= phase.damage_multiplier;
+        self.defense *= phase.defense_multiplier;
+        self.current_health += phase.heal;
+        if let Some(cooldown) = phase.special_attack_cooldown {
+            self.special_attack_cooldown = cooldown;
         }
     }
 
     pub fn should_enter_next_phase(&self) -> bool {
         let health_percentage = self.get_health_percentage();
-        match self.boss_type {
-            BossType::SamosGuardian => self.phase == 1 && health_percentage <= 50.0,
-            BossType::TyrantOfTyre => self.phase == 1 && health_percentage <= 40.0,
-            BossType::BabylonianSage => self.phase == 1 && health_percentage <= 30.0,
-            BossType::FinalBoss => {
-                (self.phase == 1 && health_percentage <= 60.0)
-                    || (self.phase == 2 && health_percentage <= 25.0)
-            }
-            BossType::None => false,
-        }
+        self.definition()
+            .and_then(|definition| definition.phases.get(self.phase as usize - 1))
+            .is_some_and(|phase| health_percentage <= phase.health_threshold)
     }
 }
 
@@ -241,6 +229,13 @@ pub enum FightOption {
     Mercy,
 }
 
+// Brief highlight of the boss portrait when something happens to it
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum PortraitFlash {
+    Hit,
+    Special,
+}
+
 #[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
 pub enum ShopOption {
     #[default]
@@ -250,7 +245,7 @@ pub enum ShopOption {
     Exit,
 }
 
-#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Hash, Serialize, Deserialize)]
 pub enum Places {
     #[default]
     Samos,
@@ -266,12 +261,93 @@ pub enum Places {
     Mileto,
 }
 
-#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+impl Places {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Places::Samos => "Samos",
+            Places::SabbiaSamos => "Sabbia di Samos",
+            Places::Tiro => "Tiro",
+            Places::ColonneTiro => "Colonne di Tiro",
+            Places::Crotone => "Crotone",
+            Places::ScuolaCrotone => "Scuola di Crotone",
+            Places::Babilonia => "Babilonia",
+            Places::BabiloniaBoss => "Palazzo di Babilonia",
+            Places::Olimpia => "Olimpia",
+            Places::Syros => "Syros",
+            Places::Mileto => "Mileto",
+        }
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
 pub enum StoryState {
     #[default]
     First,
     Second,
     Third,
+    // Final chapters, reached once the Ombra del Caos has been dealt with
+    Reconciliation,
+    Conquest,
+}
+
+impl StoryState {
+    pub fn chapter(&self) -> &'static str {
+        match self {
+            StoryState::First => "I",
+            StoryState::Second => "II",
+            StoryState::Third => "III",
+            StoryState::Reconciliation | StoryState::Conquest => "IV",
+        }
+    }
+}
+
+const DIALOGUE_CHAR_INTERVAL: Duration = Duration::from_millis(30);
+const PORTRAIT_FLASH_DURATION: Duration = Duration::from_millis(250);
+// When choosing the ending, a boss weighs as much as this many regular enemies
+const BOSS_ROUTE_WEIGHT: u32 = 3;
+
+// Shared by the player's and the bosses' health bars
+fn health_color(percentage: f64) -> Color {
+    if percentage > 60.0 {
+        Color::Green
+    } else if percentage > 30.0 {
+        Color::Yellow
+    } else {
+        Color::Red
+    }
+}
+
+// Appended to the battle messages when a move is resisted or hits a weak spot
+fn resistance_note(resistance: f64) -> &'static str {
+    if resistance < 1.0 {
+        " Resiste al colpo."
+    } else if resistance > 1.0 {
+        " È il suo punto debole!"
+    } else {
+        ""
+    }
+}
+
+// e.g. "Resiste: Jab | Debole: Montante"; empty when no move stands out
+fn describe_resistances(resistance: impl Fn(MoveKind) -> f64) -> String {
+    let names = |matches: &dyn Fn(f64) -> bool| {
+        MoveKind::ALL
+            .iter()
+            .filter(|kind| matches(resistance(**kind)))
+            .map(|kind| kind.name())
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let resisted = names(&|factor| factor < 1.0);
+    let weak = names(&|factor| factor > 1.0);
+    let mut parts = vec![];
+    if !resisted.is_empty() {
+        parts.push(format!("Resiste: {}", resisted));
+    }
+    if !weak.is_empty() {
+        parts.push(format!("Debole: {}", weak));
+    }
+    parts.join(" | ")
 }
 
 #[derive(Debug, Default, Clone)]
@@ -285,36 +361,71 @@ pub struct App {
     pub player_strength: f64,
     pub player_dmg: f64,
     pub player_health: f64,
+    pub player_max_health: f64,
     pub player_def: f64,
     pub player_xp: f64,
     pub player_lvl: f64,
     pub player_player_place: Places,
     pub player_heal_value: f64,
     pub player_heal_factor: f64,
+    pub player_wisdom: f64,
+    // Earned on level-up and not yet spent
+    pub stat_points: u32,
+    // Points placed on the allocation screen, in order, until confirmed
+    pub pending_stat_points: Vec<StatKind>,
+    pub selected_stat: usize,
     pub player_xp_factor: f64,
-    pub player_inventory: Vec<String>,
+    pub player_inventory: Inventory,
+    pub player_equipment: Equipment,
+    pub selected_inventory_item: usize,
+    pub player_statuses: StatusEffects,
+    pub player_stamina: f64,
+    pub player_max_stamina: f64,
+    pub player_gold: u32,
+    pub selected_shop_item: usize,
+    pub market: Market,
+    pub selected_destination: usize,
+    pub pending_boss: Option<String>,
+    pub defeated_bosses: Vec<String>,
+    pub spared_bosses: Vec<String>,
+    pub enemies_spared: u32,
+    pub enemies_defeated: u32,
+    // Grows when opponents are spared and drops when bosses are killed
+    pub player_reputation: i32,
+    pub battle_return_state: Option<GameState>,
     pub selected_fight_option: FightOption,
+    pub selected_move: MoveKind,
+    pub turn_queue: VecDeque<Turn>,
+    pub player_defending: bool,
     pub selected_shop_option: ShopOption,
-    pub enemy_health: f64,
-    pub enemy_strength: f64,
-    pub enemy_dmg: f64,
-    pub enemy_strength_factor: f64,
-    pub enemy_heal: f64,
-    pub enemy_is_alive: bool,
-    pub mercy_outcome: Option<bool>,
+    pub save_menu_mode: SaveMenuMode,
+    pub selected_save_slot: usize,
+    // Read from disk once when the save menu opens, so browsing it stays cheap
+    pub save_entries: Vec<(String, PathBuf)>,
+    pub save_summaries: Vec<String>,
+    pub current_enemy: Enemy,
+    pub mercy_outcome: Option<MercyOutcome>,
     pub message_log: Vec<String>,
     pub boss_dialogue_index: usize,
     pub boss_dialogue: Vec<String>,
+    // Characters of the current line typed out so far
+    pub dialogue_typed: usize,
+    // Held back until the boss has finished its last words
+    pub pending_outcome: Option<BattleOutcome>,
+    pub portrait_flash: Option<(PortraitFlash, Instant)>,
     pub is_boss_battle: bool,
+    // Set once the story is started or a save is loaded; until then there is
+    // nothing worth autosaving
+    pub run_started: bool,
     pub current_boss: Boss, // New field for message history
 }
 
-#[allow(deprecated)]
 #[allow(dead_code)]
 impl App {
     pub fn new() -> Self {
         Self {
-            player_health: 1000.0,
+            player_health: 100.0,
+            player_max_health: 100.0,
             player_strength: 1.0,
             player_dmg: 15.0,
             player_def: 5.0,
@@ -322,57 +433,87 @@ impl App {
             player_lvl: 1.0,
             player_heal_value: 20.0,
             player_heal_factor: 1.0,
+            player_stamina: 50.0,
+            player_max_stamina: 50.0,
             player_xp_factor: 1.0,
-            enemy_health: 150.0,
-            enemy_strength: 1.1,
-            enemy_dmg: 10.0,
-            enemy_heal: 15.0,
+            player_gold: 50,
             saved_state_before_inventory: None,
             mercy_outcome: None,
             message_log: vec!["Benvenuto nel mondo di Pitagora!".to_string()],
-            current_boss: Boss::new(BossType::FinalBoss),
-            is_boss_battle: true,
+            current_boss: Boss::new(BossType::None),
+            is_boss_battle: false,
             boss_dialogue: vec![],
             boss_dialogue_index: 0,
             ..Self::default()
         }
     }
 
-    pub fn start_boss_battle(&mut self, boss_type: BossType) {
-        self.current_boss = Boss::new(boss_type);
+    pub fn start_boss_battle(&mut self, boss_id: &str) {
+        self.current_boss = Boss::from_id(boss_id);
         self.is_boss_battle = true;
+        self.battle_return_state = None;
+        self.player_stamina = self.player_max_stamina;
         self.game_state = GameState::Battle;
+        self.pending_outcome = None;
+        self.portrait_flash = None;
+        self.boss_dialogue.clear();
         self.boss_dialogue_index = 0;
-
-        // Set boss-specific dialogue
-        self.boss_dialogue = match boss_type {
-            BossType::SamosGuardian => vec![
-                "Chi osa disturbare l'antica saggezza di Samos?".to_string(),
-                "I segreti geometrici non sono per i deboli!".to_string(),
-                "Dimostra la tua conoscenza in battaglia!".to_string(),
-            ],
-            BossType::TyrantOfTyre => vec![
-                "Un altro sfidante si presenta davanti al mio trono!".to_string(),
-                "Nessuno può sfidare il mio potere a Tiro!".to_string(),
-                "Preparati a cadere davanti alla mia ira!".to_string(),
-            ],
-            BossType::BabylonianSage => vec![
-                "Ah, un giovane studioso cerca la conoscenza antica...".to_string(),
-                "Ma prima devi dimostrare di essere degno!".to_string(),
-                "I misteri di Babilonia non si rivelano facilmente!".to_string(),
-            ],
-            BossType::FinalBoss => vec![
-                "Così... hai raggiunto la fine del tuo viaggio...".to_string(),
-                "Io sono tutto ciò che si oppone all'ordine e alla ragione!".to_string(),
-                "Preparati ad affrontare il CAOS ASSOLUTO!".to_string(),
-            ],
-            BossType::None => vec![],
-        };
+        if let Some(definition) = self.current_boss.definition() {
+            self.queue_boss_dialogue(&definition.dialogue);
+        }
 
         let boss_name = self.current_boss.name.clone();
         self.add_message(format!("Boss battle iniziata: {}!", boss_name));
     }
 
+    // Lines are spoken one at a time; no turn is played while any are left
+    fn queue_boss_dialogue(&mut self, lines: &[String]) {
+        if !self.dialogue_pending() {
+            self.boss_dialogue.clear();
+            self.boss_dialogue_index = 0;
+            self.dialogue_typed = 0;
+        }
+        self.boss_dialogue.extend(lines.iter().cloned());
+    }
+
+    fn dialogue_pending(&self) -> bool {
+        self.boss_dialogue_index < self.boss_dialogue.len()
+    }
+
+    fn is_typing_dialogue(&self) -> bool {
+        self.boss_dialogue
+            .get(self.boss_dialogue_index)
+            .is_some_and(|line| self.dialogue_typed < line.chars().count())
+    }
+
+    // The first press finishes typing the line, the next one moves on; once
+    // the boss is done talking the battle picks up where it stopped
+    fn advance_dialogue(&mut self) {
+        if self.is_typing_dialogue() {
+            self.dialogue_typed = usize::MAX;
+            return;
+        }
+        self.boss_dialogue_index += 1;
+        self.dialogue_typed = 0;
+        if self.dialogue_pending() {
+            return;
+        }
+        match self.pending_outcome.take() {
+            Some(outcome) => self.finish_battle(outcome),
+            None => self.run_turns(),
+        }
+    }
+
+    fn flash_portrait(&mut self, flash: PortraitFlash) {
+        self.portrait_flash = Some((flash, Instant::now()));
+    }
+
+    fn active_portrait_flash(&self) -> Option<PortraitFlash> {
+        self.portrait_flash
+            .filter(|(_, started)| started.elapsed() < PORTRAIT_FLASH_DURATION)
+            .map(|(flash, _)| flash)
+    }
+
     // Enhanced battle rendering for bosses
     fn render_boss_battle(&mut self, frame: &mut Frame, area: Rect) {
         let battle_layout = Layout::default()
@@ -398,20 +539,18 @@ impl App {
         let boss = &self.current_boss;
         let health_percentage = boss.get_health_percentage();
 
-        // Health bar color based on percentage
-        let health_color = if health_percentage > 60.0 {
-            Color::Green
-        } else if health_percentage > 30.0 {
-            Color::Yellow
-        } else {
-            Color::Red
-        };
+        let health_color = health_color(health_percentage);
+
+        // The portrait, when the boss has one, takes the left side of the box
+        let portrait = content::portrait_for_boss(&boss.id);
+        let portrait_width = portrait.map_or(0, |portrait| portrait.width() as u16 + 2);
+        let info_width = area.width.saturating_sub(portrait_width);
 
         // Create health bar
-        let health_bar_width = (area.width as f64 * (health_percentage / 100.0)) as u16;
-        let health_bar = "█".repeat(health_bar_width.min(area.width.saturating_sub(4)) as usize);
+        let health_bar_width = (info_width as f64 * (health_percentage / 100.0)) as u16;
+        let health_bar = "█".repeat(health_bar_width.min(info_width.saturating_sub(4)) as usize);
 
-        let boss_info = vec![
+        let mut boss_info = vec![
             Line::from(boss.name.clone()).bold().red().centered(),
             Line::from(""),
             Line::from(format!("Fase: {}", boss.phase))
@@ -436,34 +575,86 @@ impl App {
             .cyan()
             .centered(),
         ];
+        if let Some(definition) = boss.definition() {
+            let resistances = describe_resistances(|kind| definition.resistance(kind));
+            if !resistances.is_empty() {
+                boss_info.push(Line::from(resistances).dark_gray().centered());
+            }
+        }
+        if !boss.statuses.is_empty() {
+            boss_info.push(
+                Line::from(format!("Effetti: {}", boss.statuses.labels().join(", ")))
+                    .magenta()
+                    .centered(),
+            );
+        }
 
         let boss_block = Block::bordered()
             .title(Line::from(" BOSS BATTLE ").bold().red())
             .border_style(Style::default().red());
+        let inner = boss_block.inner(area);
+        frame.render_widget(boss_block, area);
+
+        let Some(portrait) = portrait else {
+            frame.render_widget(
+                Paragraph::new(boss_info).alignment(Alignment::Center),
+                inner,
+            );
+            return;
+        };
+
+        let [portrait_area, info_area] =
+            Layout::horizontal([Length(portrait_width), Fill(1)]).areas(inner);
+
+        // The portrait darkens as the fight goes on and flashes on hits
+        let portrait_style = match self.active_portrait_flash() {
+            Some(PortraitFlash::Hit) => Style::default().white().on_red(),
+            Some(PortraitFlash::Special) => Style::default().black().on_magenta(),
+            None => match boss.phase {
+                1 => Style::default().white(),
+                2 => Style::default().yellow(),
+                _ => Style::default().red(),
+            },
+        };
+        let portrait_lines: Vec<Line> = portrait
+            .for_phase(boss.phase)
+            .iter()
+            .map(|line| Line::from(line.as_str()))
+            .collect();
+        let portrait_height = (portrait_lines.len() as u16).min(portrait_area.height);
+        let [portrait_area] = Layout::vertical([Length(portrait_height)])
+            .flex(layout::Flex::Center)
+            .areas(portrait_area);
 
         frame.render_widget(
-            Paragraph::new(boss_info)
-                .block(boss_block)
-                .alignment(Alignment::Center),
-            area,
+            Paragraph::new(portrait_lines).style(portrait_style),
+            portrait_area.inner(Margin::new(1, 0)),
+        );
+        frame.render_widget(
+            Paragraph::new(boss_info).alignment(Alignment::Center),
+            info_area,
         );
     }
 
     fn render_boss_dialogue(&mut self, frame: &mut Frame, area: Rect) {
-        let dialogue_text = if self.boss_dialogue_index < self.boss_dialogue.len() {
+        let dialogue_text = if self.dialogue_pending() {
+            let typed: String = self.boss_dialogue[self.boss_dialogue_index]
+                .chars()
+                .take(self.dialogue_typed)
+                .collect();
+            let hint = if self.is_typing_dialogue() {
+                "Premi Invio o Spazio per saltare..."
+            } else {
+                "Premi Invio o Spazio per continuare..."
+            };
             vec![
                 Line::from(""),
-                Line::from(format!(
-                    "\"{}\"",
-                    self.boss_dialogue[self.boss_dialogue_index]
-                ))
-                .italic()
-                .white()
-                .centered(),
-                Line::from(""),
-                Line::from("Premi Spazio per continuare...")
-                    .dark_gray()
+                Line::from(format!("\"{}\"", typed))
+                    .italic()
+                    .white()
                     .centered(),
+                Line::from(""),
+                Line::from(hint).dark_gray().centered(),
             ]
         } else {
             vec![
@@ -473,7 +664,9 @@ impl App {
                     .red()
                     .centered(),
                 Line::from(""),
-                Line::from("Scegli la tua azione...").dark_gray().centered(),
+                Line::from("Scegli la tua azione... (↑/↓ o J/G/M per il colpo)")
+                    .dark_gray()
+                    .centered(),
             ]
         };
 
@@ -489,167 +682,145 @@ impl App {
         );
     }
 
-    // Enhanced attack logic for boss battles
-    fn logic_boss_attack(&mut self) {
-        let mut damage_dealt = self.player_dmg;
-        let boss_defense = self.current_boss.defense;
-
-        // Apply boss defense
-        damage_dealt = (damage_dealt - boss_defense).max(1.0);
-
-        self.current_boss.current_health -= damage_dealt;
-        self.add_message(format!("Hai inflitto {:.0} danni al boss!", damage_dealt));
-
-        // Check if boss should enter next phase
+    // Moves the boss to its next phase once its health drops low enough
+    fn check_boss_phase(&mut self) {
         if self.current_boss.should_enter_next_phase() {
-            let old_phase = self.current_boss.phase;
             self.current_boss.enter_next_phase();
             self.add_message(format!(
                 "{} entra nella fase {}!",
                 self.current_boss.name, self.current_boss.phase
             ));
+            let phase = self
+                .current_boss
+                .definition()
+                .and_then(|definition| definition.phases.get(self.current_boss.phase as usize - 2));
+            if let Some(phase) = phase {
+                self.queue_boss_dialogue(&phase.dialogue);
+            }
         }
+    }
 
-        // Check if boss is defeated
-        if self.current_boss.current_health <= 0.0 {
-            self.current_boss.defeated = true;
-            self.is_boss_battle = false;
-            self.logic_boss_victory();
+    fn check_boss_low_health(&mut self) {
+        let Some(definition) = self.current_boss.definition() else {
+            return;
+        };
+        if self.current_boss.low_health_spoken
+            || self.current_boss.is_defeated()
+            || self.current_boss.get_health_percentage() > definition.low_health_threshold
+        {
             return;
         }
-
-        // Boss counterattack
-        self.logic_boss_counterattack();
+        self.current_boss.low_health_spoken = true;
+        self.queue_boss_dialogue(&definition.low_health_dialogue);
     }
 
-    fn logic_boss_counterattack(&mut self) {
+    fn logic_boss_turn(&mut self) {
         // Extract the values we need first, before any mutable borrowing
+        self.current_boss.tick_cooldown();
         let boss_name = self.current_boss.name.clone();
-        let boss_damage = self.current_boss.damage;
         let is_special_ready = self.current_boss.is_special_ready();
 
         // Decide if boss uses special attack
-        let use_special = is_special_ready && rand::thread_rng().gen_bool(0.6);
+        let use_special = is_special_ready && rand::rng().random_bool(battle::BOSS_SPECIAL_CHANCE);
 
         if use_special {
             self.logic_boss_special_attack();
         } else {
             // Normal attack
-            let mut damage = boss_damage;
-            // Add some randomness
-            damage *= rand::thread_rng().gen_range(0.8..1.2);
-            self.player_health -= damage;
-            self.add_message(format!("{} ti attacca per {:.0} danni!", boss_name, damage));
-        }
-
-        // Check if player is defeated
-        if self.player_health <= 0.0 {
-            self.game_state = GameState::GameOver;
+            let hit = self.hit_player(self.current_boss.attack());
+            self.add_message(format!(
+                "{} ti attacca per {:.0} danni!{}",
+                boss_name,
+                hit.damage,
+                hit.critical_note()
+            ));
         }
     }
 
+    // What the special does comes entirely from the boss definition
     fn logic_boss_special_attack(&mut self) {
-        // Clone/copy the values we need before borrowing mutably
+        let Some(definition) = self.current_boss.definition() else {
+            return;
+        };
+        let special = &definition.special;
         let boss_name = self.current_boss.name.clone();
-        let boss_type = self.current_boss.boss_type.clone(); // Assuming BossType implements Clone
-        let boss_damage = self.current_boss.damage;
-        let boss_max_health = self.current_boss.max_health;
-
-        match boss_type {
-            BossType::SamosGuardian => {
-                // Scudo Geometrico - reduces incoming damage
-                let damage = boss_damage * 1.5;
-                self.player_health -= damage;
-                self.add_message(format!(
-                    "{} usa Scudo Geometrico! {:.0} danni!",
-                    boss_name, damage
-                ));
-            }
-            BossType::TyrantOfTyre => {
-                // Ira del Tiranno - ignores player defense
-                let damage = boss_damage * 2.0;
-                self.player_health -= damage;
-                self.add_message(format!(
-                    "{} scatena la sua Ira! {:.0} danni devastanti!",
-                    boss_name, damage
-                ));
-            }
-            BossType::BabylonianSage => {
-                // Teorema Antico - heals and buffs
-                self.current_boss.current_health += 50.0;
-                self.current_boss.current_health =
-                    self.current_boss.current_health.min(boss_max_health);
-                self.current_boss.damage *= 1.1;
-                self.add_message(format!("{} usa un Teorema Antico! Si rafforza!", boss_name));
+        let special_name = definition.special_name();
+        self.flash_portrait(PortraitFlash::Special);
+
+        let hit = (special.damage_multiplier > 0.0).then(|| {
+            let attack = Attack::new(self.current_boss.attack().power * special.damage_multiplier);
+            if special.ignore_defense {
+                self.hit_player(attack.ignoring_defense())
+            } else {
+                self.hit_player(attack)
             }
-            BossType::FinalBoss => {
-                // Caos Numerico - massive damage and debuff
-                let damage = boss_damage * 2.5;
-                self.player_health -= damage;
-                self.player_dmg *= 0.9; // Temporary debuff
-                self.add_message(format!(
-                    "{} scatena il Caos Numerico! {:.0} danni! Sei indebolito!",
-                    boss_name, damage
-                ));
+        });
+        if special.heal > 0.0 {
+            self.current_boss.current_health =
+                (self.current_boss.current_health + special.heal).min(self.current_boss.max_health);
+        }
+        if let Some(effect) = special.effect {
+            let status =
+                StatusEffect::new(effect.kind, special_name, effect.magnitude, effect.turns);
+            match effect.target {
+                SpecialTarget::Boss => self.current_boss.statuses.apply(status),
+                SpecialTarget::Player => self.player_statuses.apply(status),
             }
-            BossType::None => {}
         }
+
+        let damage = format!("{:.0}", hit.map_or(0.0, |hit| hit.damage));
+        let message = match (special.message.is_empty(), hit) {
+            (false, _) => special
+                .message
+                .replace("{boss}", &boss_name)
+                .replace("{damage}", &damage),
+            (true, Some(_)) => format!("{} usa {}! {} danni!", boss_name, special_name, damage),
+            (true, None) => format!("{} usa {}!", boss_name, special_name),
+        };
+        self.add_message(message);
         self.current_boss.use_special_attack();
     }
 
     fn logic_boss_victory(&mut self) {
-        let boss_type = self.current_boss.boss_type.clone();
-        let boss_name = self.current_boss.name.clone();
-
-        // Give rewards based on boss type
-        match boss_type {
-            BossType::SamosGuardian => {
-                self.player_xp += 100.0;
-                self.player_inventory
-                    .push("Frammento Geometrico di Samos".to_string());
-                self.add_message("Hai sconfitto il Guardiano di Samos!".to_string());
-            }
-            BossType::TyrantOfTyre => {
-                self.player_xp += 200.0;
-                self.player_dmg += 5.0;
-                self.player_inventory.push("Corona del Tiranno".to_string());
-                self.add_message("Hai liberato Tiro dal tiranno!".to_string());
-            }
-            BossType::BabylonianSage => {
-                self.player_xp += 300.0;
-                self.player_heal_factor += 0.5;
-                self.player_inventory
-                    .push("Tavoletta Babilonese Antica".to_string());
-                self.add_message("Hai ottenuto la saggezza babilonese!".to_string());
+        let boss_type = self.current_boss.boss_type;
+        self.current_boss.defeated = true;
+        self.defeated_bosses.push(self.current_boss.id.clone());
+        self.player_reputation += battle::BOSS_KILL_REPUTATION;
+        self.market.record_battle();
+
+        // XP, trophy and message come from the content file
+        if let Some(definition) = self.current_boss.definition() {
+            if let Some(item) = &definition.reward_item {
+                self.player_inventory.add(item, 1);
             }
-            BossType::FinalBoss => {
-                self.player_xp += 500.0;
-                self.player_lvl += 1.0;
-                self.player_inventory
-                    .push("Cristallo dell'Ordine".to_string());
-                self.add_message(
-                    "Hai sconfitto il Caos! Sei un vero seguace di Pitagora!".to_string(),
-                );
-            }
-            BossType::None => {}
+            self.add_message(format!(
+                "{} +{:.0} XP.",
+                definition.victory_message,
+                self.scaled_xp(definition.xp_reward)
+            ));
+            self.gain_xp(definition.xp_reward);
         }
 
-        self.game_state = GameState::Story;
+        // Defeating the Ombra del Caos is worth a level on its own; the other
+        // bosses reward the player through their trophies
+        if boss_type == BossType::FinalBoss {
+            self.level_up();
+        }
     }
 
-    // Method to trigger boss battles based on location
+    // Asks the player whether to face the boss guarding the current place, if
+    // it has not been defeated yet
     pub fn check_for_boss_encounter(&mut self) {
-        let boss_type = match self.player_player_place {
-            Places::SabbiaSamos => BossType::SamosGuardian,
-            Places::ColonneTiro => BossType::TyrantOfTyre,
-            Places::BabiloniaBoss => BossType::BabylonianSage,
-            Places::Olimpia => BossType::FinalBoss,
-            _ => BossType::None,
+        let Some(definition) = content::boss_for_place(self.player_player_place) else {
+            return;
         };
-
-        if boss_type != BossType::None {
-            self.start_boss_battle(boss_type);
+        if self.defeated_bosses.contains(&definition.id)
+            || self.spared_bosses.contains(&definition.id)
+        {
+            return;
         }
+        self.pending_boss = Some(definition.id.clone());
+        self.game_state = GameState::BossEncounter;
     }
 
     pub fn run(mut self, mut terminal: DefaultTerminal) -> Result<()> {
@@ -694,14 +865,20 @@ impl App {
         match self.game_state {
             GameState::MainMenu => self.render_main_menu(frame, area),
             GameState::Story => self.render_story(frame, area),
-            GameState::Fight => self.render_battle(frame, area),
             GameState::Shop => self.render_shop(frame, area),
+            GameState::ShopBuy => self.render_shop_buy(frame, area),
+            GameState::ShopSell => self.render_shop_sell(frame, area),
+            GameState::Travel => self.render_travel(frame, area),
+            GameState::BossEncounter => self.render_boss_encounter(frame, area),
             GameState::Inventory => self.render_inventory(frame, area),
             GameState::Mercy => self.render_mercy(frame, area),
             GameState::GameOver => self.render_game_over(frame, area),
+            GameState::Epilogue => self.render_epilogue(frame, area),
+            GameState::StatPoints => self.render_stat_points(frame, area),
             GameState::Heal => self.render_heal(frame, area),
             GameState::Minigame => self.render_minigame(frame, area),
             GameState::Test => self.render_test(frame, area),
+            GameState::SaveSlots => self.render_save_slots(frame, area),
             GameState::Battle => {
                 if self.is_boss_battle {
                     self.render_boss_battle(frame, area);
@@ -713,42 +890,105 @@ impl App {
     }
 
     fn render_player_stats(&mut self, frame: &mut Frame, area: Rect) {
-        let max_hp = 100.0 + (self.player_lvl - 1.0) * 20.0;
-        let hp_percentage = (self.player_health / max_hp * 100.0) as u8;
+        let max_hp = self.max_health();
+        let hp_ratio = if max_hp > 0.0 {
+            (self.player_health / max_hp).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        let xp_needed = progression::xp_to_next_level(self.player_lvl);
 
-        let stats_text = vec![
-            Line::from(vec![
-                "HP: ".into(),
-                format!("{:.0}/{:.0}", self.player_health, max_hp)
-                    .red()
-                    .bold(),
-                format!(" ({}%)", hp_percentage).dark_gray(),
-            ]),
+        let mut stats_text = vec![
             Line::from(vec![
                 "LVL: ".into(),
                 format!("{:.0}", self.player_lvl).yellow().bold(),
                 " | XP: ".into(),
-                format!("{:.0}", self.player_xp).cyan(),
+                format!("{:.0}/{:.0}", self.player_xp, xp_needed).cyan(),
             ]),
             Line::from(vec![
                 "ATK: ".into(),
-                format!("{:.0}", self.player_dmg).red(),
+                format!("{:.0}", self.total_damage()).red(),
                 " | DEF: ".into(),
-                format!("{:.0}", self.player_def).blue(),
+                format!("{:.0}", self.total_defense()).blue(),
+                " | Oro: ".into(),
+                format!("{}", self.player_gold).yellow(),
+            ]),
+            Line::from(vec![
+                "Luogo: ".into(),
+                self.get_place_name().green().bold(),
+                " | Reputazione: ".into(),
+                format!("{:+}", self.player_reputation).magenta(),
             ]),
-            Line::from(vec!["Luogo: ".into(), self.get_place_name().green().bold()]),
         ];
+        if !self.player_statuses.is_empty() {
+            stats_text.push(Line::from(vec![
+                "Effetti: ".into(),
+                self.player_statuses.labels().join(", ").magenta(),
+            ]));
+        }
 
         let stats_block = Block::bordered()
             .title(" Statistiche Giocatore ")
             .title_style(Style::default().yellow().bold())
             .border_style(Style::default().cyan());
 
+        let inner = stats_block.inner(area);
+        frame.render_widget(stats_block, area);
+        let [hp_area, text_area, gauge_area] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .areas(inner);
         frame.render_widget(
-            Paragraph::new(stats_text)
-                .block(stats_block)
-                .wrap(Wrap { trim: false }),
-            area,
+            Gauge::default()
+                .label(format!(
+                    "HP {:.0}/{:.0}",
+                    self.player_health.max(0.0),
+                    max_hp
+                ))
+                .gauge_style(
+                    Style::default()
+                        .fg(health_color(hp_ratio * 100.0))
+                        .on_dark_gray(),
+                )
+                .ratio(hp_ratio),
+            hp_area,
+        );
+        // XP and stamina share the bottom row to leave room for the stats
+        let [xp_area, stamina_area] =
+            Layout::horizontal([Constraint::Fill(1), Constraint::Fill(1)])
+                .spacing(2)
+                .areas(gauge_area);
+        frame.render_widget(
+            Paragraph::new(stats_text).wrap(Wrap { trim: false }),
+            text_area,
+        );
+
+        frame.render_widget(
+            LineGauge::default()
+                .label(format!("XP verso LVL {:.0}", self.player_lvl + 1.0))
+                .filled_style(Style::default().cyan())
+                .unfilled_style(Style::default().dark_gray())
+                .ratio((self.player_xp / xp_needed).clamp(0.0, 1.0)),
+            xp_area,
+        );
+
+        let stamina_ratio = if self.player_max_stamina > 0.0 {
+            (self.player_stamina / self.player_max_stamina).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        frame.render_widget(
+            LineGauge::default()
+                .label(format!(
+                    "Stamina {:.0}/{:.0}",
+                    self.player_stamina, self.player_max_stamina
+                ))
+                .filled_style(Style::default().green())
+                .unfilled_style(Style::default().dark_gray())
+                .ratio(stamina_ratio),
+            stamina_area,
         );
     }
 
@@ -781,19 +1021,7 @@ impl App {
     }
 
     fn get_place_name(&self) -> &str {
-        match self.player_player_place {
-            Places::Samos => "Samos",
-            Places::SabbiaSamos => "Sabbia di Samos",
-            Places::Tiro => "Tiro",
-            Places::ColonneTiro => "Colonne di Tiro",
-            Places::Crotone => "Crotone",
-            Places::ScuolaCrotone => "Scuola di Crotone",
-            Places::Babilonia => "Babilonia",
-            Places::BabiloniaBoss => "Palazzo di Babilonia",
-            Places::Olimpia => "Olimpia",
-            Places::Syros => "Syros",
-            Places::Mileto => "Mileto",
-        }
+        self.player_player_place.name()
     }
 
     fn render_main_menu(&mut self, frame: &mut Frame, area: Rect) {
@@ -811,6 +1039,13 @@ impl App {
             Line::from(""),
             Line::from("(S) Inizia Storia | (H) Guarigione | (W) Negozio").centered(),
             Line::from("(I) Inventario | (T) Test | (E) Esci").centered(),
+            Line::from("(C) Carica Partita | (P) Salva Partita").centered(),
+            Line::from(if self.stat_points > 0 {
+                format!("(A) Punti Abilità - {} da assegnare!", self.stat_points)
+            } else {
+                "(A) Punti Abilità".to_string()
+            })
+            .centered(),
         ];
 
         frame.render_widget(
@@ -832,22 +1067,46 @@ impl App {
             .split(area);
 
         // Render enemy information
+        let enemy = &self.current_enemy;
         let enemy_title = Line::from("Battaglia Imminente!").bold().red().centered();
+        let resistances = enemy
+            .definition()
+            .map(|definition| describe_resistances(|kind| definition.resistance(kind)))
+            .unwrap_or_default();
         let enemy_text = vec![
             Line::from(""),
-            Line::from("Un Brigante selvaggio appare!").centered(),
-            Line::from(""),
-            Line::from(format!("Salute del Brigante: {:.0}", self.enemy_health))
-                .red()
+            Line::from(format!("{} appare!", enemy.name))
+                .bold()
+                .centered(),
+            Line::from(enemy.description.clone())
+                .italic()
+                .dark_gray()
                 .centered(),
             Line::from(""),
-            Line::from("Prepara la tua mossa...").dark_gray().centered(),
+            Line::from(format!(
+                "Salute: {:.0}/{:.0}",
+                enemy.health.max(0.0),
+                enemy.max_health
+            ))
+            .red()
+            .centered(),
+            Line::from(format!(
+                "Attacco: {:.0} | Difesa: {:.0}",
+                enemy.damage, enemy.defense
+            ))
+            .dark_gray()
+            .centered(),
+            Line::from(resistances).dark_gray().centered(),
+            Line::from("Prepara la tua mossa... (↑/↓ o J/G/M per il colpo | (F) Fuggi)")
+                .dark_gray()
+                .centered(),
         ];
 
         frame.render_widget(
             Paragraph::new(enemy_text)
                 .block(Block::bordered().title(enemy_title))
-                .alignment(Alignment::Center),
+                .alignment(Alignment::Center)
+                .wrap(Wrap { trim: false }),
             battle_layout[0],
         );
 
@@ -874,13 +1133,24 @@ impl App {
             }
         };
 
-        // Attack option
-        let attack_text = Line::from("ATTACCA")
-            .style(get_option_style(
-                FightOption::Attack,
-                self.selected_fight_option,
+        // Attack option, with the move that Enter will use
+        let selected_move = self.selected_move;
+        let attack_text = vec![
+            Line::from(format!("ATTACCA: {}", selected_move.name()))
+                .style(get_option_style(
+                    FightOption::Attack,
+                    self.selected_fight_option,
+                ))
+                .centered(),
+            Line::from(format!(
+                "Prec. {:.0}% | x{:.1} | {:.0} st",
+                selected_move.accuracy() * 100.0,
+                selected_move.damage_multiplier(),
+                selected_move.stamina_cost()
             ))
-            .centered();
+            .dark_gray()
+            .centered(),
+        ];
         frame.render_widget(
             Paragraph::new(attack_text)
                 .block(Block::bordered())
@@ -945,7 +1215,7 @@ impl App {
                     Line::from("Nato a Samos, iniziò il suo viaggio verso la saggezza").centered(),
                     Line::from("studiando presso i saggi dell'Oriente.").centered(),
                 ],
-                Line::from("Premi (C) per continuare | (B) per Battaglia | (M) per Menu")
+                Line::from("(C) Continua | (B) Battaglia | (E) Esplora | (T) Viaggia | (M) Menu")
                     .dark_gray()
                     .centered(),
             ),
@@ -959,7 +1229,7 @@ impl App {
                     Line::from("Ma il suo destino lo chiamava in Magna Grecia,").centered(),
                     Line::from("dove avrebbe fondato la sua famosa scuola.").centered(),
                 ],
-                Line::from("Premi (C) per continuare | (B) per Battaglia | (M) per Menu")
+                Line::from("(C) Continua | (B) Battaglia | (E) Esplora | (T) Viaggia | (M) Menu")
                     .dark_gray()
                     .centered(),
             ),
@@ -978,7 +1248,42 @@ impl App {
                         .bold()
                         .centered(),
                 ],
-                Line::from("Storia completata! | (B) per Battaglia | (M) per Menu")
+                Line::from(
+                    "Storia completata! | (B) Battaglia | (E) Esplora | (T) Viaggia | (M) Menu",
+                )
+                .dark_gray()
+                .centered(),
+            ),
+            StoryState::Reconciliation => (
+                vec![
+                    Line::from(""),
+                    Line::from("L'Ombra del Caos si è ritirata senza odio.").centered(),
+                    Line::from("Chi hai risparmiato lungo la strada torna a Crotone:").centered(),
+                    Line::from("briganti, pirati e sapienti siedono alla stessa tavola.")
+                        .centered(),
+                    Line::from(""),
+                    Line::from("Capitolo IV - La Riconciliazione")
+                        .green()
+                        .bold()
+                        .centered(),
+                ],
+                Line::from("(C) Epilogo | (B) Battaglia | (E) Esplora | (T) Viaggia | (M) Menu")
+                    .dark_gray()
+                    .centered(),
+            ),
+            StoryState::Conquest => (
+                vec![
+                    Line::from(""),
+                    Line::from("L'Ombra del Caos è stata spezzata con la forza.").centered(),
+                    Line::from("Le città del Mediterraneo si piegano al tuo nome,").centered(),
+                    Line::from("ma nei loro occhi vedi più paura che rispetto.").centered(),
+                    Line::from(""),
+                    Line::from("Capitolo IV - La Conquista")
+                        .red()
+                        .bold()
+                        .centered(),
+                ],
+                Line::from("(C) Epilogo | (B) Battaglia | (E) Esplora | (T) Viaggia | (M) Menu")
                     .dark_gray()
                     .centered(),
             ),
@@ -1071,12 +1376,122 @@ impl App {
         }
     }
 
-    fn get_shop_name(&self) -> &str {
-        match self.player_player_place {
-            Places::Samos => "Negozio di Samos",
-            Places::SabbiaSamos => "Bancarella nella Sabbia di Samos",
-            Places::Tiro => "Emporio di Tiro",
-            Places::ColonneTiro => "Mercato delle Colonne di Tiro",
+    fn render_shop_buy(&mut self, frame: &mut Frame, area: Rect) {
+        let title = Line::from(format!(" {} - Compra ", self.get_shop_name()))
+            .bold()
+            .blue()
+            .centered();
+
+        let entries = self.shop_entries();
+        let mut lines = vec![
+            Line::from(format!("Oro disponibile: {}", self.player_gold))
+                .yellow()
+                .centered(),
+            Line::from(""),
+        ];
+
+        if entries.is_empty() {
+            lines.push(Line::from("Il negozio non ha nulla da vendere.").centered());
+        }
+        let place = self.player_player_place;
+        for (i, entry) in entries.iter().enumerate() {
+            let price = self.market.buy_price(place, entry);
+            let stock = self.market.stock(place, entry);
+            let text = if stock == 0 {
+                format!("{} - esaurito", entry.item)
+            } else {
+                format!("{} - {} oro (disponibili: {})", entry.item, price, stock)
+            };
+            let line = if i == self.selected_shop_item {
+                Line::from(format!("> {} <", text)).yellow().bold()
+            } else if stock == 0 || price > self.player_gold {
+                Line::from(text).dark_gray()
+            } else {
+                Line::from(text).white()
+            };
+            lines.push(line.centered());
+        }
+
+        if let Some(definition) = entries
+            .get(self.selected_shop_item)
+            .and_then(|entry| content::item_by_name(&entry.item))
+        {
+            lines.push(Line::from(""));
+            lines.push(
+                Line::from(definition.description.clone())
+                    .italic()
+                    .cyan()
+                    .centered(),
+            );
+        }
+
+        lines.push(Line::from(""));
+        lines.push(
+            Line::from("Frecce per scegliere, Invio per comprare | (B) per tornare")
+                .dark_gray()
+                .centered(),
+        );
+
+        frame.render_widget(
+            Paragraph::new(lines)
+                .block(Block::bordered().title(title))
+                .alignment(Alignment::Center)
+                .wrap(Wrap { trim: false }),
+            area,
+        );
+    }
+
+    fn render_shop_sell(&mut self, frame: &mut Frame, area: Rect) {
+        let title = Line::from(format!(" {} - Vendi ", self.get_shop_name()))
+            .bold()
+            .blue()
+            .centered();
+
+        let mut lines = vec![
+            Line::from(format!("Oro disponibile: {}", self.player_gold))
+                .yellow()
+                .centered(),
+            Line::from(""),
+        ];
+
+        if self.player_inventory.is_empty() {
+            lines.push(Line::from("Non hai nulla da vendere.").centered());
+        }
+        for (i, item) in self.player_inventory.items.iter().enumerate() {
+            let price = self
+                .market
+                .sell_price(self.player_player_place, &item.name, item.value);
+            let text = format!("{} x{} - {} oro", item.name, item.quantity, price);
+            let line = if i == self.selected_inventory_item {
+                Line::from(format!("> {} <", text)).yellow().bold()
+            } else {
+                Line::from(text).white()
+            };
+            lines.push(line.centered());
+        }
+
+        lines.push(Line::from(""));
+        lines.push(
+            Line::from("Frecce per scegliere, Invio per vendere | (B) per tornare")
+                .dark_gray()
+                .centered(),
+        );
+
+        frame.render_widget(
+            Paragraph::new(lines)
+                .block(Block::bordered().title(title))
+                .alignment(Alignment::Center)
+                .wrap(Wrap { trim: false }),
+            area,
+        );
+    }
+
+    fn get_shop_name(&self) -> &str {
+        match self.player_player_place {
+            Places::Samos => "Negozio di Samos",
+            Places::SabbiaSamos => "Bancarella nella Sabbia di Samos",
+            Places::Tiro => "Emporio di Tiro",
+            Places::ColonneTiro => "Mercato delle Colonne di Tiro",
             Places::Crotone => "Bottega di Crotone",
             Places::ScuolaCrotone => "Spaccio della Scuola di Crotone",
             Places::Babilonia => "Mercante Babilonese",
@@ -1094,18 +1509,59 @@ impl App {
             .border_style(Style::default().fg(Color::DarkGray));
 
         let mut inventory_lines: Vec<Line> = vec![Line::from("")];
+        let mut worn = vec![];
+        for slot in EquipSlot::ALL {
+            if !worn.is_empty() {
+                worn.push(" | ".into());
+            }
+            let name = self.player_equipment.get(slot).unwrap_or("-");
+            worn.push(format!("{}: ", slot.name()).dark_gray());
+            worn.push(name.to_string().magenta());
+        }
+        inventory_lines.push(Line::from(worn).centered());
+        inventory_lines.push(Line::from(""));
 
         if self.player_inventory.is_empty() {
             inventory_lines.push(Line::from("Il tuo inventario è vuoto.").centered());
         } else {
-            for (i, item) in self.player_inventory.iter().enumerate() {
-                inventory_lines.push(Line::from(format!("• {}", item)).white());
+            for (i, item) in self.player_inventory.items.iter().enumerate() {
+                let text = format!("{} x{} - Valore: {}", item.name, item.quantity, item.value);
+                if i == self.selected_inventory_item {
+                    inventory_lines.push(Line::from(format!("> {} <", text)).yellow().bold());
+                } else {
+                    inventory_lines.push(Line::from(format!("• {}", text)).white());
+                }
+            }
+
+            if let Some(item) = self.player_inventory.get(self.selected_inventory_item) {
+                inventory_lines.push(Line::from(""));
+                inventory_lines.push(Line::from(item.description.clone()).italic().cyan());
+                if item.usable {
+                    inventory_lines.push(Line::from("Utilizzabile").green());
+                }
+                if let Some(equipment) =
+                    content::item_by_name(&item.name).and_then(|item| item.equipment)
+                {
+                    inventory_lines.push(
+                        Line::from(format!(
+                            "{}: {}",
+                            equipment.slot.name(),
+                            equipment.bonus.describe()
+                        ))
+                        .magenta(),
+                    );
+                }
             }
         }
 
         inventory_lines.push(Line::from(""));
         inventory_lines.push(
-            Line::from("Premi (B) per tornare indietro.")
+            Line::from("Frecce per selezionare | (U) per usare | (B) per tornare indietro.")
+                .dark_gray()
+                .centered(),
+        );
+        inventory_lines.push(
+            Line::from("(E) Indossa | Togli: (1) Arma (2) Armatura (3) Reliquia")
                 .dark_gray()
                 .centered(),
         );
@@ -1120,38 +1576,76 @@ impl App {
     }
 
     fn render_mercy(&mut self, frame: &mut Frame, area: Rect) {
+        let opponent = self.opponent().name().to_string();
         let title_text = match self.mercy_outcome {
-            Some(true) => Line::from("Pietà Accettata!").bold().green().centered(),
-            Some(false) => Line::from("Pietà Rifiutata!").bold().red().centered(),
+            Some(MercyOutcome::Accepted) => {
+                Line::from("Pietà Accettata!").bold().green().centered()
+            }
+            Some(MercyOutcome::Rejected) => Line::from("Pietà Rifiutata!").bold().red().centered(),
+            Some(MercyOutcome::Refused) => Line::from("Nessuna Pietà!").bold().red().centered(),
             None => Line::from("Tenta la Pietà...").bold().blue().centered(),
         };
+        let spare_message = self
+            .current_boss
+            .definition()
+            .and_then(|definition| definition.mercy.as_ref())
+            .filter(|_| self.is_boss_battle)
+            .map(|mercy| mercy.message.clone())
+            .unwrap_or_default();
 
         let message_text = match self.mercy_outcome {
-            Some(true) => vec![
+            Some(MercyOutcome::Accepted) => vec![
                 Line::from(""),
-                Line::from("Il brigante si ritira.").green().centered(),
+                Line::from(format!("{} si ritira.", opponent))
+                    .green()
+                    .centered(),
                 Line::from("La battaglia è terminata pacificamente.")
                     .green()
                     .centered(),
+                Line::from(spare_message).italic().centered(),
                 Line::from(""),
                 Line::from("Premi Invio per continuare")
                     .dark_gray()
                     .centered(),
             ],
-            Some(false) => vec![
+            Some(MercyOutcome::Rejected) => vec![
                 Line::from(""),
-                Line::from("Il brigante rifiuta la tua pietà!")
+                Line::from(format!("{} rifiuta la tua pietà!", opponent))
+                    .red()
+                    .centered(),
+                Line::from("La battaglia continua e il nemico ne approfitta.")
                     .red()
                     .centered(),
-                Line::from("La battaglia continua.").red().centered(),
                 Line::from(""),
                 Line::from("Premi Invio per continuare")
                     .dark_gray()
                     .centered(),
             ],
+            Some(MercyOutcome::Refused) => vec![
+                Line::from(""),
+                Line::from(format!("{} non conosce pietà.", opponent))
+                    .red()
+                    .centered(),
+                Line::from("Dovrai combattere fino alla fine.")
+                    .red()
+                    .centered(),
+                Line::from(""),
+                Line::from("Premi Invio per tornare alla battaglia")
+                    .dark_gray()
+                    .centered(),
+            ],
             None => vec![
                 Line::from(""),
                 Line::from("Offri pietà al tuo nemico?").centered(),
+                Line::from(format!(
+                    "Probabilità che accetti: {:.0}%",
+                    self.mercy_chance().unwrap_or_default() * 100.0
+                ))
+                .yellow()
+                .centered(),
+                Line::from("Più è ferito, più sei esperto e rispettato, più è facile che accetti.")
+                    .dark_gray()
+                    .centered(),
                 Line::from(""),
                 Line::from("Premi Invio per offrire pietà")
                     .dark_gray()
@@ -1165,7 +1659,8 @@ impl App {
         frame.render_widget(
             Paragraph::new(message_text)
                 .block(Block::bordered().title(title_text))
-                .alignment(Alignment::Center),
+                .alignment(Alignment::Center)
+                .wrap(Wrap { trim: false }),
             area,
         );
     }
@@ -1189,15 +1684,150 @@ impl App {
         );
     }
 
+    fn render_epilogue(&mut self, frame: &mut Frame, area: Rect) {
+        let (title, mut epilogue_text) = match self.story_state {
+            StoryState::Reconciliation => (
+                Line::from(" Epilogo - La Riconciliazione ")
+                    .bold()
+                    .green()
+                    .centered(),
+                vec![
+                    Line::from(""),
+                    Line::from("La scuola di Crotone apre le porte a chiunque voglia imparare.")
+                        .centered(),
+                    Line::from("Gli antichi nemici insegnano accanto ai tuoi discepoli,")
+                        .centered(),
+                    Line::from("e persino il caos trova posto nell'armonia dei numeri.").centered(),
+                    Line::from(""),
+                    Line::from("\"Tutto è numero, e ogni numero ha il suo posto.\"")
+                        .italic()
+                        .centered(),
+                ],
+            ),
+            StoryState::Conquest => (
+                Line::from(" Epilogo - La Conquista ")
+                    .bold()
+                    .red()
+                    .centered(),
+                vec![
+                    Line::from(""),
+                    Line::from("Il tuo nome è inciso su ogni colonna da Samos a Babilonia.")
+                        .centered(),
+                    Line::from("La scuola di Crotone prospera sotto la tua guardia,").centered(),
+                    Line::from("ma i suoi allievi imparano a temere prima che a capire.")
+                        .centered(),
+                    Line::from(""),
+                    Line::from("\"L'ordine imposto non è armonia.\"")
+                        .italic()
+                        .centered(),
+                ],
+            ),
+            _ => (Line::from(" Epilogo ").bold().centered(), vec![]),
+        };
+
+        epilogue_text.extend([
+            Line::from(""),
+            Line::from(format!(
+                "Nemici risparmiati: {} | sconfitti: {}",
+                self.enemies_spared, self.enemies_defeated
+            ))
+            .yellow()
+            .centered(),
+            Line::from(format!(
+                "Boss risparmiati: {} | sconfitti: {}",
+                self.spared_bosses.len(),
+                self.defeated_bosses.len()
+            ))
+            .yellow()
+            .centered(),
+            Line::from(format!("Reputazione: {:+}", self.player_reputation))
+                .magenta()
+                .centered(),
+            Line::from(""),
+            Line::from("Premi Invio per tornare alla storia | (M) Menu")
+                .dark_gray()
+                .centered(),
+        ]);
+
+        frame.render_widget(
+            Paragraph::new(epilogue_text)
+                .block(Block::bordered().title(title))
+                .alignment(Alignment::Center)
+                .wrap(Wrap { trim: false }),
+            area,
+        );
+    }
+
+    fn render_stat_points(&mut self, frame: &mut Frame, area: Rect) {
+        let title = Line::from(" Punti Abilità ").bold().yellow().centered();
+        let remaining = self.stat_points - self.pending_stat_points.len() as u32;
+
+        let mut stat_lines = vec![
+            Line::from(""),
+            Line::from(format!("Punti da assegnare: {}", remaining))
+                .yellow()
+                .bold()
+                .centered(),
+            Line::from(""),
+        ];
+        for (i, kind) in StatKind::ALL.iter().enumerate() {
+            let pending = self.pending_count(*kind);
+            let mut spans = vec![
+                format!("{}: ", kind.name()).into(),
+                kind.format(self.stat_value(*kind)).bold(),
+            ];
+            if pending > 0 {
+                let raised = self.stat_value(*kind) + kind.per_point() * pending as f64;
+                spans.push(format!(" -> {}", kind.format(raised)).green());
+                spans.push(format!(" (+{})", pending).dark_gray());
+            }
+            let line = Line::from(spans).centered();
+            if i == self.selected_stat {
+                stat_lines.push(line.style(Style::default().black().on_yellow()));
+                stat_lines.push(Line::from(kind.description()).dark_gray().centered());
+            } else {
+                stat_lines.push(line);
+            }
+        }
+        stat_lines.extend([
+            Line::from(""),
+            Line::from("Su/Giù per scegliere | Destra (+) assegna | Sinistra (-) toglie")
+                .dark_gray()
+                .centered(),
+            Line::from("(U) Annulla l'ultimo | Invio conferma | (B) Indietro senza salvare")
+                .dark_gray()
+                .centered(),
+        ]);
+
+        frame.render_widget(
+            Paragraph::new(stat_lines)
+                .block(Block::bordered().title(title))
+                .alignment(Alignment::Center)
+                .wrap(Wrap { trim: false }),
+            area,
+        );
+    }
+
     fn render_heal(&mut self, frame: &mut Frame, area: Rect) {
         let title = Line::from("Centro di Guarigione").bold().green().centered();
         let heal_text = vec![
             Line::from(""),
             Line::from("Ti trovi in un luogo sacro di guarigione.").centered(),
             Line::from(""),
-            Line::from(format!("Salute attuale: {:.0}", self.player_health))
-                .yellow()
-                .centered(),
+            Line::from(format!(
+                "Salute attuale: {:.0}/{:.0}",
+                self.player_health,
+                self.max_health()
+            ))
+            .yellow()
+            .centered(),
+            Line::from(""),
+            Line::from(format!(
+                "Ogni cura ripristina {:.0} salute",
+                self.player_heal_value * self.heal_power()
+            ))
+            .green()
+            .centered(),
             Line::from(""),
             Line::from("Premi (H) per guarire | (M) per Menu")
                 .dark_gray()
@@ -1255,7 +1885,177 @@ impl App {
         );
     }
 
+    fn render_save_slots(&mut self, frame: &mut Frame, area: Rect) {
+        let title = match self.save_menu_mode {
+            SaveMenuMode::Load => Line::from(" Carica Partita ").bold().blue().centered(),
+            SaveMenuMode::Save => Line::from(" Salva Partita ").bold().green().centered(),
+        };
+
+        let mut slot_lines = vec![Line::from("")];
+        for (i, summary) in self.save_summaries.iter().enumerate() {
+            let line = if i == self.selected_save_slot {
+                Line::from(format!("> {} <", summary)).yellow().bold()
+            } else {
+                Line::from(summary.as_str()).white()
+            };
+            slot_lines.push(line.centered());
+        }
+
+        slot_lines.push(Line::from(""));
+        slot_lines.push(
+            Line::from("Frecce per scegliere, Invio per confermare | (B) per tornare")
+                .dark_gray()
+                .centered(),
+        );
+
+        frame.render_widget(
+            Paragraph::new(slot_lines)
+                .block(Block::bordered().title(title))
+                .alignment(Alignment::Center),
+            area,
+        );
+    }
+
+    fn render_boss_encounter(&mut self, frame: &mut Frame, area: Rect) {
+        let boss = self
+            .pending_boss
+            .as_deref()
+            .map(Boss::from_id)
+            .unwrap_or_else(|| Boss::new(BossType::None));
+        let title = Line::from(" Presenza Minacciosa ").bold().red().centered();
+        let encounter_text = vec![
+            Line::from(""),
+            Line::from(format!("{} ti sbarra la strada!", boss.name))
+                .red()
+                .bold()
+                .centered(),
+            Line::from(""),
+            Line::from(boss.description.clone()).italic().centered(),
+            Line::from(""),
+            Line::from(format!("Abilità: {}", boss.special_ability))
+                .cyan()
+                .centered(),
+            Line::from(""),
+            Line::from("Vuoi affrontarlo?").bold().centered(),
+            Line::from(""),
+            Line::from("(S) Affronta | (N) Non ora")
+                .dark_gray()
+                .centered(),
+        ];
+
+        frame.render_widget(
+            Paragraph::new(encounter_text)
+                .block(
+                    Block::bordered()
+                        .title(title)
+                        .border_style(Style::default().red()),
+                )
+                .alignment(Alignment::Center)
+                .wrap(Wrap { trim: false }),
+            area,
+        );
+    }
+
+    fn render_travel(&mut self, frame: &mut Frame, area: Rect) {
+        let travel_layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Percentage(70), // Map
+                Constraint::Percentage(30), // Destinations
+            ])
+            .split(area);
+
+        let current = self.player_player_place;
+        let destinations = map::neighbours(current);
+        let selected = destinations.get(self.selected_destination).copied();
+
+        let map_canvas = Canvas::default()
+            .block(Block::bordered().title(Line::from(" Mappa del Mediterraneo ").bold().blue()))
+            .marker(symbols::Marker::Braille)
+            .x_bounds(map::LON_BOUNDS)
+            .y_bounds(map::LAT_BOUNDS)
+            .paint(move |ctx| {
+                ctx.draw(&Map {
+                    color: Color::DarkGray,
+                    resolution: MapResolution::High,
+                });
+                ctx.layer();
+
+                for (from, to) in map::ROUTES {
+                    let is_selected = (from == current && Some(to) == selected)
+                        || (to == current && Some(from) == selected);
+                    let (x1, y1) = map::coordinates(from);
+                    let (x2, y2) = map::coordinates(to);
+                    ctx.draw(&CanvasLine {
+                        x1,
+                        y1,
+                        x2,
+                        y2,
+                        color: if is_selected {
+                            Color::Yellow
+                        } else {
+                            Color::Blue
+                        },
+                    });
+                }
+
+                for place in map::PLACES {
+                    let (x, y) = map::coordinates(place);
+                    let label = if place == current {
+                        Line::from(format!("@ {}", place.name())).green().bold()
+                    } else if Some(place) == selected {
+                        Line::from(format!("• {}", place.name())).yellow().bold()
+                    } else {
+                        Line::from("•").white()
+                    };
+                    ctx.print(x, y, label);
+                }
+            });
+        frame.render_widget(map_canvas, travel_layout[0]);
+
+        let mut destination_lines = vec![
+            Line::from(format!("Sei a {}", current.name())).green(),
+            Line::from(""),
+            Line::from("Destinazioni:").bold(),
+        ];
+        for (i, destination) in destinations.iter().enumerate() {
+            if i == self.selected_destination {
+                destination_lines.push(
+                    Line::from(format!("> {}", destination.name()))
+                        .yellow()
+                        .bold(),
+                );
+            } else {
+                destination_lines.push(Line::from(format!("  {}", destination.name())).white());
+            }
+        }
+        destination_lines.push(Line::from(""));
+        destination_lines.push(Line::from("Frecce per scegliere").dark_gray());
+        destination_lines.push(Line::from("Invio per partire").dark_gray());
+        destination_lines.push(Line::from("(B) per tornare").dark_gray());
+
+        frame.render_widget(
+            Paragraph::new(destination_lines)
+                .block(Block::bordered().title(" Viaggio "))
+                .wrap(Wrap { trim: false }),
+            travel_layout[1],
+        );
+    }
+
     fn handle_crossterm_events(&mut self) -> Result<()> {
+        // While a line is being typed out or the portrait flashes, redraw on a
+        // timer instead of waiting for a key
+        if self.is_typing_dialogue() && !event::poll(DIALOGUE_CHAR_INTERVAL)? {
+            self.dialogue_typed += 1;
+            return Ok(());
+        }
+        if let Some((_, started)) = self.portrait_flash {
+            let remaining = PORTRAIT_FLASH_DURATION.saturating_sub(started.elapsed());
+            if !event::poll(remaining)? {
+                self.portrait_flash = None;
+                return Ok(());
+            }
+        }
         match event::read()? {
             Event::Key(key) if key.kind == KeyEventKind::Press => self.on_key_event(key),
             Event::Mouse(_) => {}
@@ -1286,51 +2086,32 @@ impl App {
                     _ => {}
                 },
 
-                GameState::Fight => match key.code {
-                    // Changed to GameState::Battle
-                    KeyCode::Left => {
-                        self.selected_fight_option = match self.selected_fight_option {
-                            FightOption::Attack => FightOption::Mercy, // Loop from Attack to Run
-                            FightOption::Defend => FightOption::Attack,
-                            FightOption::Inventory => FightOption::Defend,
-                            FightOption::Mercy => FightOption::Inventory,
-                        };
-                    }
-                    KeyCode::Right => {
-                        self.selected_fight_option = match self.selected_fight_option {
-                            FightOption::Attack => FightOption::Defend,
-                            FightOption::Defend => FightOption::Inventory,
-                            FightOption::Inventory => FightOption::Mercy,
-                            FightOption::Mercy => FightOption::Attack, // Loop from Run to Attack
-                        };
-                    }
-                    KeyCode::Char('G') => self.logic_hook(),
-                    KeyCode::Char('J') => self.logic_jab(),
-                    KeyCode::Char('M') => self.logic_montante(),
-
+                GameState::Mercy => match key.code {
+                    KeyCode::Enter | KeyCode::Char(' ') => match self.mercy_outcome {
+                        Some(outcome) => self.close_mercy(outcome),
+                        None => self.logic_mercy(),
+                    },
+                    KeyCode::Char('B') | KeyCode::Char('b') => match self.mercy_outcome {
+                        Some(outcome) => self.close_mercy(outcome),
+                        None => self.game_state = GameState::Battle,
+                    },
                     _ => {}
                 },
-
-                GameState::Mercy => match key.code {
-                    KeyCode::Enter | KeyCode::Char(' ') => {
-                        // If mercy was successful, go to Story, otherwise return to Battle
-                        match self.mercy_outcome {
-                            Some(true) => self.game_state = GameState::Story,
-                            Some(false) => self.game_state = GameState::Battle,
-                            None => {} // Should not happen, but no action if outcome not determined
-                        }
-                        self.mercy_outcome = None; // Reset mercy outcome after action
-                    }
-                    KeyCode::Char('B') | KeyCode::Char('b') => {
-                        self.game_state = GameState::Battle;
-                        self.mercy_outcome = None; // Reset mercy outcome if returning to battle
+                GameState::Epilogue => match key.code {
+                    KeyCode::Enter
+                    | KeyCode::Char(' ')
+                    | KeyCode::Char('B')
+                    | KeyCode::Char('b') => self.game_state = GameState::Story,
+                    KeyCode::Char('M') | KeyCode::Char('m') => {
+                        self.game_state = GameState::MainMenu
                     }
                     _ => {}
                 },
-                GameState::GameOver => match key.code {
-                    KeyCode::Char('E') | KeyCode::Char('e') => self.logic_quit(),
-                    _ => {}
-                },
+                GameState::GameOver => {
+                    if let KeyCode::Char('E') | KeyCode::Char('e') = key.code {
+                        self.logic_quit()
+                    }
+                }
 
                 GameState::Heal => match key.code {
                     KeyCode::Char('M') | KeyCode::Char('m') => {
@@ -1345,6 +2126,7 @@ impl App {
                 GameState::MainMenu => match key.code {
                     KeyCode::Char('S') | KeyCode::Char('s') => {
                         self.game_state = GameState::Story;
+                        self.run_started = true;
                         self.add_message("Iniziata la storia di Pitagora!".to_string());
                     }
                     KeyCode::Char('E') | KeyCode::Char('e') => {
@@ -1363,6 +2145,60 @@ impl App {
                         self.game_state = GameState::Shop;
                         self.add_message("Benvenuto nel negozio!".to_string());
                     }
+                    KeyCode::Char('C') | KeyCode::Char('c') => {
+                        self.open_save_slots(SaveMenuMode::Load);
+                    }
+                    KeyCode::Char('P') | KeyCode::Char('p') => {
+                        self.open_save_slots(SaveMenuMode::Save);
+                    }
+                    KeyCode::Char('A') | KeyCode::Char('a') => {
+                        self.selected_stat = 0;
+                        self.pending_stat_points.clear();
+                        self.game_state = GameState::StatPoints;
+                    }
+                    _ => {}
+                },
+                GameState::StatPoints => match key.code {
+                    KeyCode::Up => {
+                        self.selected_stat = self
+                            .selected_stat
+                            .checked_sub(1)
+                            .unwrap_or(StatKind::ALL.len() - 1);
+                    }
+                    KeyCode::Down => {
+                        self.selected_stat = (self.selected_stat + 1) % StatKind::ALL.len();
+                    }
+                    KeyCode::Right | KeyCode::Char('+') => self.logic_assign_stat_point(),
+                    KeyCode::Left | KeyCode::Char('-') => self.logic_remove_stat_point(),
+                    KeyCode::Char('U') | KeyCode::Char('u') | KeyCode::Backspace => {
+                        self.pending_stat_points.pop();
+                    }
+                    KeyCode::Enter => self.logic_confirm_stat_points(),
+                    // Leaving without confirming discards the pending points
+                    KeyCode::Char('B') | KeyCode::Char('b') => {
+                        self.pending_stat_points.clear();
+                        self.game_state = GameState::MainMenu;
+                    }
+                    _ => {}
+                },
+                GameState::SaveSlots => match key.code {
+                    KeyCode::Up => {
+                        self.selected_save_slot = self
+                            .selected_save_slot
+                            .checked_sub(1)
+                            .unwrap_or(self.save_entries.len() - 1);
+                    }
+                    KeyCode::Down => {
+                        self.selected_save_slot =
+                            (self.selected_save_slot + 1) % self.save_entries.len();
+                    }
+                    KeyCode::Enter | KeyCode::Char(' ') => match self.save_menu_mode {
+                        SaveMenuMode::Save => self.logic_save_slot(),
+                        SaveMenuMode::Load => self.logic_load_slot(),
+                    },
+                    KeyCode::Char('B') | KeyCode::Char('b') => {
+                        self.game_state = GameState::MainMenu
+                    }
                     _ => {}
                 },
                 GameState::Story => match key.code {
@@ -1372,11 +2208,13 @@ impl App {
                                 self.story_state = StoryState::Second;
                                 self.add_message("Continui il viaggio di Pitagora...".to_string());
                                 self.player_player_place = Places::Samos;
+                                self.autosave();
                             }
                             StoryState::Second => {
                                 self.story_state = StoryState::Third;
                                 self.add_message("Il destino di Pitagora si compie...".to_string());
                                 self.player_player_place = Places::Babilonia;
+                                self.autosave();
                             }
                             StoryState::Third => {
                                 // Story is complete, maybe unlock something or provide different options
@@ -1388,15 +2226,20 @@ impl App {
 
                                 self.player_player_place = Places::Crotone;
                             }
+                            StoryState::Reconciliation | StoryState::Conquest => {
+                                self.game_state = GameState::Epilogue
+                            }
                         }
                     }
                     KeyCode::Char('H') | KeyCode::Char('h') => self.game_state = GameState::Heal,
                     KeyCode::Char('V') | KeyCode::Char('v') => {
                         self.game_state = GameState::GameOver
                     }
-                    KeyCode::Char('B') | KeyCode::Char('b') => {
-                        self.game_state = GameState::Battle;
-                        self.add_message("Una battaglia sta per iniziare!".to_string());
+                    KeyCode::Char('B') | KeyCode::Char('b') => self.start_battle(GameState::Story),
+                    KeyCode::Char('E') | KeyCode::Char('e') => self.logic_explore(),
+                    KeyCode::Char('T') | KeyCode::Char('t') => {
+                        self.selected_destination = 0;
+                        self.game_state = GameState::Travel;
                     }
                     KeyCode::Char('M') | KeyCode::Char('m') => {
                         self.game_state = GameState::MainMenu
@@ -1421,40 +2264,45 @@ impl App {
                             FightOption::Mercy => FightOption::Attack,
                         };
                     }
+                    KeyCode::Up if self.selected_fight_option == FightOption::Attack => {
+                        let index = MoveKind::ALL
+                            .iter()
+                            .position(|kind| *kind == self.selected_move)
+                            .unwrap_or_default();
+                        self.selected_move =
+                            MoveKind::ALL[(index + MoveKind::ALL.len() - 1) % MoveKind::ALL.len()];
+                    }
+                    KeyCode::Down if self.selected_fight_option == FightOption::Attack => {
+                        let index = MoveKind::ALL
+                            .iter()
+                            .position(|kind| *kind == self.selected_move)
+                            .unwrap_or_default();
+                        self.selected_move = MoveKind::ALL[(index + 1) % MoveKind::ALL.len()];
+                    }
+                    // The boss has its say before the fight starts
+                    KeyCode::Enter | KeyCode::Char(' ') if self.dialogue_pending() => {
+                        self.advance_dialogue()
+                    }
+                    KeyCode::Char('J') | KeyCode::Char('j') => {
+                        self.player_action(Action::Move(MoveKind::Jab))
+                    }
+                    KeyCode::Char('G') | KeyCode::Char('g') => {
+                        self.player_action(Action::Move(MoveKind::Hook))
+                    }
+                    KeyCode::Char('M') | KeyCode::Char('m') => {
+                        self.player_action(Action::Move(MoveKind::Montante))
+                    }
+                    KeyCode::Char('F') | KeyCode::Char('f') => self.player_action(Action::Flee),
                     KeyCode::Enter | KeyCode::Char(' ') => match self.selected_fight_option {
-                        FightOption::Attack => self.logic_attack(),
-                        FightOption::Defend => self.logic_defend(),
+                        FightOption::Attack => self.player_action(Action::Move(self.selected_move)),
+                        FightOption::Defend => self.player_action(Action::Defend),
                         FightOption::Inventory => {
                             self.previous_game_state = Some(self.game_state);
                             self.game_state = GameState::Inventory
                         }
-                        FightOption::Mercy => {
-                            self.game_state = GameState::Mercy;
-                            self.logic_mercy();
-                        }
+                        FightOption::Mercy => self.open_mercy(),
                     },
 
-                    KeyCode::Char(' ') => {
-                        if self.is_boss_battle
-                            && self.boss_dialogue_index < self.boss_dialogue.len()
-                        {
-                            self.boss_dialogue_index += 1;
-                        } else {
-                            // Normal battle action handling
-                            match self.selected_fight_option {
-                                FightOption::Attack => {
-                                    if self.is_boss_battle {
-                                        self.logic_boss_attack();
-                                    } else {
-                                        self.logic_attack();
-                                    }
-                                } //
-
-                                _ => {}
-                            }
-                        }
-                    }
-
                     _ => {}
                 },
                 GameState::Shop => match key.code {
@@ -1476,13 +2324,22 @@ impl App {
                     }
                     KeyCode::Enter | KeyCode::Char(' ') => {
                         match self.selected_shop_option {
-                            ShopOption::Buy => self.logic_buy(),
-                            ShopOption::Sell => self.logic_sell(),
+                            ShopOption::Buy => {
+                                self.selected_shop_item = 0;
+                                self.game_state = GameState::ShopBuy;
+                            }
+                            ShopOption::Sell => {
+                                self.clamp_inventory_cursor();
+                                self.game_state = GameState::ShopSell;
+                            }
                             ShopOption::Inventory => {
                                 self.previous_game_state = Some(self.game_state); // Save current state
                                 self.game_state = GameState::Inventory
                             }
-                            ShopOption::Exit => self.logic_quit(),
+                            ShopOption::Exit => {
+                                self.game_state = GameState::MainMenu;
+                                self.add_message("Arrivederci!".to_string());
+                            }
                         }
                     }
                     KeyCode::Char('M') | KeyCode::Char('m') => {
@@ -1490,7 +2347,77 @@ impl App {
                     }
                     _ => {}
                 },
+                GameState::BossEncounter => match key.code {
+                    KeyCode::Char('S') | KeyCode::Char('s') | KeyCode::Enter => {
+                        if let Some(boss_id) = self.pending_boss.take() {
+                            self.start_boss_battle(&boss_id);
+                        }
+                    }
+                    KeyCode::Char('N') | KeyCode::Char('n') => {
+                        if let Some(boss_id) = self.pending_boss.take() {
+                            let boss_name = Boss::from_id(&boss_id).name;
+                            self.add_message(format!("Per ora eviti {}.", boss_name));
+                        }
+                        self.game_state = GameState::Story;
+                    }
+                    _ => {}
+                },
+                GameState::Travel => match key.code {
+                    KeyCode::Up => {
+                        self.selected_destination = self.selected_destination.saturating_sub(1);
+                    }
+                    KeyCode::Down
+                        if self.selected_destination + 1
+                            < map::neighbours(self.player_player_place).len() =>
+                    {
+                        self.selected_destination += 1;
+                    }
+                    KeyCode::Enter | KeyCode::Char(' ') => self.logic_travel(),
+                    KeyCode::Char('B') | KeyCode::Char('b') => self.game_state = GameState::Story,
+                    _ => {}
+                },
+                GameState::ShopBuy => match key.code {
+                    KeyCode::Up => {
+                        self.selected_shop_item = self.selected_shop_item.saturating_sub(1);
+                    }
+                    KeyCode::Down if self.selected_shop_item + 1 < self.shop_entries().len() => {
+                        self.selected_shop_item += 1;
+                    }
+                    KeyCode::Enter | KeyCode::Char(' ') => self.logic_buy(),
+                    KeyCode::Char('B') | KeyCode::Char('b') => self.game_state = GameState::Shop,
+                    _ => {}
+                },
+                GameState::ShopSell => match key.code {
+                    KeyCode::Up => {
+                        self.selected_inventory_item =
+                            self.selected_inventory_item.saturating_sub(1);
+                    }
+                    KeyCode::Down
+                        if self.selected_inventory_item + 1 < self.player_inventory.len() =>
+                    {
+                        self.selected_inventory_item += 1;
+                    }
+                    KeyCode::Enter | KeyCode::Char(' ') => self.logic_sell(),
+                    KeyCode::Char('B') | KeyCode::Char('b') => self.game_state = GameState::Shop,
+                    _ => {}
+                },
                 GameState::Inventory => match key.code {
+                    KeyCode::Up => {
+                        self.selected_inventory_item =
+                            self.selected_inventory_item.saturating_sub(1);
+                    }
+                    KeyCode::Down
+                        if self.selected_inventory_item + 1 < self.player_inventory.len() =>
+                    {
+                        self.selected_inventory_item += 1;
+                    }
+                    KeyCode::Enter | KeyCode::Char('U') | KeyCode::Char('u') => {
+                        self.logic_use_item()
+                    }
+                    KeyCode::Char('E') | KeyCode::Char('e') => self.logic_equip_item(),
+                    KeyCode::Char('1') => self.logic_unequip(EquipSlot::Weapon),
+                    KeyCode::Char('2') => self.logic_unequip(EquipSlot::Armor),
+                    KeyCode::Char('3') => self.logic_unequip(EquipSlot::Relic),
                     KeyCode::Char('B') | KeyCode::Char('b') => {
                         // Restore the previous game state if available, otherwise go to MainMenu
                         if let Some(prev_state) = self.previous_game_state.take() {
@@ -1504,58 +2431,757 @@ impl App {
             },
         }
     }
-    fn logic_attack(&mut self) {
-        self.enemy_health -= self.player_dmg;
-        if self.enemy_health <= 0.0 {
-            self.enemy_is_alive = false;
-            self.game_state = GameState::Story; // Example: go back to story after defeating enemy
+    // Starts a regular battle against an enemy of the current region; the
+    // player goes back to `return_state` once it is over
+    pub fn start_battle(&mut self, return_state: GameState) {
+        self.battle_return_state = Some(return_state);
+        self.player_stamina = self.player_max_stamina;
+        self.current_enemy = Enemy::random_for_place(self.player_player_place);
+        self.is_boss_battle = false;
+        self.selected_fight_option = FightOption::default();
+        self.game_state = GameState::Battle;
+        let enemy_name = self.current_enemy.name.clone();
+        self.add_message(format!("{} ti attacca!", enemy_name));
+    }
+    // Also true while the inventory is open from the battle menu
+    fn in_battle(&self) -> bool {
+        matches!(
+            self.game_state,
+            GameState::Battle | GameState::Mercy | GameState::BossEncounter
+        ) || self.previous_game_state == Some(GameState::Battle)
+    }
+    fn end_battle(&mut self) {
+        self.player_statuses.clear();
+        self.game_state = self.battle_return_state.take().unwrap_or(GameState::Story);
+    }
+    fn opponent(&self) -> &dyn Combatant {
+        if self.is_boss_battle {
+            &self.current_boss
+        } else {
+            &self.current_enemy
+        }
+    }
+    fn opponent_mut(&mut self) -> &mut dyn Combatant {
+        if self.is_boss_battle {
+            &mut self.current_boss
         } else {
-            self.player_health -= self.enemy_dmg;
-            if self.player_health <= 0.0 {
-                self.game_state = GameState::GameOver;
+            &mut self.current_enemy
+        }
+    }
+    // Entry point for everything the player does in battle: checks that the
+    // action is allowed, then plays the player's turn and the opponent's reply
+    fn player_action(&mut self, action: Action) {
+        if self.dialogue_pending() {
+            return;
+        }
+        match &action {
+            Action::Move(kind) => {
+                self.selected_move = *kind;
+                if !self.spend_stamina(kind.stamina_cost(), kind.name()) {
+                    return;
+                }
+            }
+            Action::Flee if self.is_boss_battle => {
+                let boss_name = self.current_boss.name.clone();
+                self.add_message(format!("Non puoi fuggire da {}!", boss_name));
+                return;
             }
+            _ => {}
         }
+        self.turn_queue.push_back(Turn::Player(action));
+        self.turn_queue.push_back(Turn::Opponent);
+        self.run_turns();
     }
-    fn logic_defend(&mut self) {
-        self.player_health -= self.enemy_dmg * 0.5; // Example: 50% damage reduction when defending
+    // Stops whenever the boss starts talking; advance_dialogue resumes it
+    fn run_turns(&mut self) {
+        while !self.dialogue_pending() {
+            let Some(turn) = self.turn_queue.pop_front() else {
+                return;
+            };
+            let outcome = match turn {
+                Turn::Player(action) => self.resolve_player_turn(action),
+                Turn::Opponent => self.resolve_opponent_turn(),
+            }
+            .or_else(|| self.battle_outcome());
+            let Some(outcome) = outcome else {
+                continue;
+            };
+            self.turn_queue.clear();
+            let defeat_dialogue = self
+                .current_boss
+                .definition()
+                .map(|definition| definition.defeat_dialogue.as_slice())
+                .unwrap_or_default();
+            if outcome == BattleOutcome::Victory
+                && self.is_boss_battle
+                && !defeat_dialogue.is_empty()
+            {
+                self.queue_boss_dialogue(defeat_dialogue);
+                self.pending_outcome = Some(outcome);
+            } else {
+                self.finish_battle(outcome);
+            }
+            return;
+        }
+    }
+    fn battle_outcome(&self) -> Option<BattleOutcome> {
         if self.player_health <= 0.0 {
+            Some(BattleOutcome::Defeat)
+        } else if self.opponent().is_defeated() {
+            Some(BattleOutcome::Victory)
+        } else {
+            None
+        }
+    }
+    fn resolve_player_turn(&mut self, action: Action) -> Option<BattleOutcome> {
+        let mut stamina = combat::STAMINA_REGEN;
+        match action {
+            Action::Move(kind) => {
+                self.resolve_move(kind);
+                self.tick_player_statuses();
+            }
+            Action::Defend => {
+                // Defending recovers more stamina than any other action
+                self.player_defending = true;
+                stamina = combat::DEFEND_STAMINA;
+                self.tick_player_statuses();
+                self.add_message("Ti metti in guardia.".to_string());
+            }
+            Action::UseItem(name) => {
+                // Effects tick first so a new buff lasts all of its turns
+                self.tick_player_statuses();
+                if let Some(effect) = content::item_by_name(&name).and_then(|item| item.effect) {
+                    self.apply_item_effect(&name, effect);
+                }
+            }
+            Action::Flee => {
+                self.tick_player_statuses();
+                if rand::rng().random_bool(battle::FLEE_CHANCE) {
+                    return Some(BattleOutcome::Fled);
+                }
+                self.add_message("Non riesci a fuggire!".to_string());
+            }
+        }
+        self.recover_stamina(stamina);
+        None
+    }
+    fn resolve_move(&mut self, kind: MoveKind) {
+        let hit = combat::resolve(
+            self.player_attack(kind),
+            self.opponent().defense(Some(kind)),
+            &mut rand::rng(),
+        );
+        let opponent_name = self.opponent().name().to_string();
+        if hit.missed {
+            self.add_message(format!("Il tuo {} manca {}!", kind.name(), opponent_name));
+            return;
+        }
+        let resistance = self.opponent().resistance(kind);
+        self.add_message(format!(
+            "{}: hai inflitto {:.0} danni a {}!{}{}",
+            kind.name(),
+            hit.damage,
+            opponent_name,
+            hit.critical_note(),
+            resistance_note(resistance)
+        ));
+        self.damage_opponent(hit.damage);
+    }
+    fn damage_opponent(&mut self, damage: f64) {
+        self.opponent_mut().take_damage(damage);
+        if self.is_boss_battle {
+            self.flash_portrait(PortraitFlash::Hit);
+            self.check_boss_phase();
+            self.check_boss_low_health();
+        }
+    }
+    fn resolve_opponent_turn(&mut self) -> Option<BattleOutcome> {
+        // Effects on the opponent wear off at the start of its turn
+        let opponent_name = self.opponent().name().to_string();
+        for effect in self.opponent_mut().statuses_mut().tick() {
+            self.add_message(format!(
+                "L'effetto di {} su {} svanisce.",
+                effect.source, opponent_name
+            ));
+        }
+
+        if self.is_boss_battle {
+            self.logic_boss_turn();
+        } else {
+            let hit = self.hit_player(self.current_enemy.attack());
+            if self.player_defending {
+                self.add_message(format!(
+                    "Ti difendi: {} ti colpisce per {:.0} danni.",
+                    opponent_name, hit.damage
+                ));
+            } else {
+                self.add_message(format!(
+                    "{} ti colpisce per {:.0} danni!{}",
+                    opponent_name,
+                    hit.damage,
+                    hit.critical_note()
+                ));
+            }
+        }
+        self.player_defending = false;
+        None
+    }
+    fn finish_battle(&mut self, outcome: BattleOutcome) {
+        self.turn_queue.clear();
+        self.player_defending = false;
+        self.boss_dialogue.clear();
+        self.boss_dialogue_index = 0;
+        let opponent_name = self.opponent().name().to_string();
+        let boss_defeated = matches!(outcome, BattleOutcome::Victory | BattleOutcome::Spared)
+            && self.is_boss_battle;
+        let story_ends = boss_defeated && self.current_boss.boss_type == BossType::FinalBoss;
+        match outcome {
+            BattleOutcome::Victory if self.is_boss_battle => self.logic_boss_victory(),
+            BattleOutcome::Victory => self.logic_enemy_victory(),
+            BattleOutcome::Defeat => {
+                self.add_message(format!("{} ti ha sconfitto...", opponent_name));
+            }
+            BattleOutcome::Fled => self.add_message(format!("Sei fuggito da {}.", opponent_name)),
+            BattleOutcome::Spared => self.logic_spare(),
+        }
+
+        self.is_boss_battle = false;
+        self.end_battle();
+        if story_ends {
+            self.enter_final_chapter();
+        }
+        if outcome == BattleOutcome::Defeat {
             self.game_state = GameState::GameOver;
         }
+        if boss_defeated {
+            self.autosave();
+        }
     }
-    fn logic_heal(&mut self) {
-        self.player_health = self.player_health * 1.2;
+    // Mercy-heavy runs end in reconciliation, every other run in conquest
+    fn is_mercy_route(&self) -> bool {
+        let spared = self.enemies_spared + BOSS_ROUTE_WEIGHT * self.spared_bosses.len() as u32;
+        let defeated =
+            self.enemies_defeated + BOSS_ROUTE_WEIGHT * self.defeated_bosses.len() as u32;
+        spared > defeated
+    }
+    fn enter_final_chapter(&mut self) {
+        self.story_state = if self.is_mercy_route() {
+            StoryState::Reconciliation
+        } else {
+            StoryState::Conquest
+        };
+        self.player_player_place = Places::Crotone;
+        self.game_state = GameState::Story;
+        self.add_message("Si apre l'ultimo capitolo della storia di Pitagora...".to_string());
+    }
+    fn scaled_xp(&self, amount: f64) -> f64 {
+        amount * self.player_xp_factor * (1.0 + progression::WISDOM_XP_BONUS * self.total_wisdom())
+    }
+    // player_xp is the progress towards the next level; a big reward can
+    // cover several level-ups at once
+    fn gain_xp(&mut self, amount: f64) {
+        self.player_xp += self.scaled_xp(amount);
+        loop {
+            let needed = progression::xp_to_next_level(self.player_lvl);
+            if self.player_xp < needed {
+                break;
+            }
+            self.player_xp -= needed;
+            self.level_up();
+        }
+    }
+    fn level_up(&mut self) {
+        let gains = progression::LEVEL_UP_GAINS;
+        self.player_lvl += 1.0;
+        self.player_max_health += gains.max_health;
+        self.heal_player(gains.max_health);
+        self.player_dmg += gains.damage;
+        self.player_def += gains.defense;
+        self.player_strength += gains.strength;
+        self.player_heal_value += gains.heal_value;
+        self.player_max_stamina += gains.max_stamina;
+        self.recover_stamina(gains.max_stamina);
+        self.stat_points += progression::STAT_POINTS_PER_LEVEL;
+        self.add_message(format!(
+            "Sei salito al livello {:.0}! {}, +{} punti abilità (A nel menù).",
+            self.player_lvl,
+            gains.describe(),
+            progression::STAT_POINTS_PER_LEVEL
+        ));
+    }
+    fn stat_value(&self, kind: StatKind) -> f64 {
+        match kind {
+            StatKind::Strength => self.player_strength,
+            StatKind::Defense => self.player_def,
+            StatKind::MaxHealth => self.player_max_health,
+            StatKind::HealPower => self.player_heal_factor,
+            StatKind::Wisdom => self.player_wisdom,
+        }
+    }
+    fn pending_count(&self, kind: StatKind) -> usize {
+        self.pending_stat_points
+            .iter()
+            .filter(|pending| **pending == kind)
+            .count()
+    }
+    fn logic_assign_stat_point(&mut self) {
+        if self.pending_stat_points.len() as u32 >= self.stat_points {
+            return;
+        }
+        self.pending_stat_points
+            .push(StatKind::ALL[self.selected_stat]);
+    }
+    // Takes back the most recent point placed on the selected stat
+    fn logic_remove_stat_point(&mut self) {
+        let kind = StatKind::ALL[self.selected_stat];
+        if let Some(index) = self
+            .pending_stat_points
+            .iter()
+            .rposition(|pending| *pending == kind)
+        {
+            self.pending_stat_points.remove(index);
+        }
+    }
+    fn logic_confirm_stat_points(&mut self) {
+        if self.pending_stat_points.is_empty() {
+            return;
+        }
+        let pending = std::mem::take(&mut self.pending_stat_points);
+        self.stat_points -= pending.len() as u32;
+        for kind in &pending {
+            let amount = kind.per_point();
+            match kind {
+                StatKind::Strength => self.player_strength += amount,
+                StatKind::Defense => self.player_def += amount,
+                StatKind::MaxHealth => {
+                    self.player_max_health += amount;
+                    self.heal_player(amount);
+                }
+                StatKind::HealPower => self.player_heal_factor += amount,
+                StatKind::Wisdom => self.player_wisdom += amount,
+            }
+        }
+        self.add_message(format!("Hai assegnato {} punti abilità.", pending.len()));
+    }
+    fn logic_enemy_victory(&mut self) {
+        let enemy = self.current_enemy.clone();
+        self.player_gold += enemy.gold_reward;
+        self.enemies_defeated += 1;
+        self.add_message(format!(
+            "Hai sconfitto {}! +{:.0} XP, +{} oro.",
+            enemy.name,
+            self.scaled_xp(enemy.xp_reward),
+            enemy.gold_reward
+        ));
+        self.gain_xp(enemy.xp_reward);
+        for item in enemy.roll_loot() {
+            self.player_inventory.add(&item, 1);
+            self.add_message(format!("{} ha lasciato: {}.", enemy.name, item));
+        }
+        self.market.record_battle();
+    }
+    // Returns false, without using up the turn, when the player is too tired
+    fn spend_stamina(&mut self, cost: f64, action: &str) -> bool {
+        if cost > self.player_stamina {
+            self.add_message(format!(
+                "Non hai abbastanza stamina per {} ({:.0} richiesta).",
+                action, cost
+            ));
+            return false;
+        }
+        self.player_stamina -= cost;
+        true
+    }
+    fn recover_stamina(&mut self, amount: f64) {
+        self.player_stamina = (self.player_stamina + amount).min(self.player_max_stamina);
+    }
+    fn effective_player_dmg(&self) -> f64 {
+        (self.total_damage() + self.player_statuses.damage_bonus())
+            * self.player_statuses.damage_dealt_factor()
+    }
+    // Base stats plus whatever the player is wearing
+    fn total_damage(&self) -> f64 {
+        self.player_dmg + self.player_equipment.bonus().damage
+    }
+    fn total_defense(&self) -> f64 {
+        self.player_def + self.player_equipment.bonus().defense
+    }
+    fn heal_power(&self) -> f64 {
+        self.player_heal_factor + self.player_equipment.bonus().heal_factor
+    }
+    fn total_wisdom(&self) -> f64 {
+        self.player_wisdom + self.player_equipment.bonus().wisdom
+    }
+    fn player_attack(&self, kind: MoveKind) -> Attack {
+        kind.attack(self.effective_player_dmg())
+            .with_strength(self.player_strength)
+    }
+    // Resolves an attack against the player and takes the damage off their health
+    fn hit_player(&mut self, attack: Attack) -> Hit {
+        let mut defense = Defense::new(
+            self.total_defense(),
+            self.player_statuses.damage_taken_factor(),
+        );
+        if self.player_defending {
+            defense.value *= combat::DEFEND_DEFENSE_FACTOR;
+            defense.damage_taken_factor *= combat::DEFEND_DAMAGE_FACTOR;
+        }
+        let hit = combat::resolve(attack, defense, &mut rand::rng());
+        self.player_health -= hit.damage;
+        hit
+    }
+    // Effects last a number of the holder's turns and expire at the end of the last one
+    fn tick_player_statuses(&mut self) {
+        for effect in self.player_statuses.tick() {
+            self.add_message(format!("L'effetto di {} svanisce.", effect.source));
+        }
+    }
+    fn logic_use_item(&mut self) {
+        let in_battle = self.previous_game_state == Some(GameState::Battle);
+        let Some(name) = self
+            .player_inventory
+            .get(self.selected_inventory_item)
+            .map(|item| item.name.clone())
+        else {
+            return;
+        };
+        let Some(definition) = content::item_by_name(&name).filter(|item| item.usable) else {
+            self.add_message(format!("{} non può essere usato.", name));
+            return;
+        };
+        let Some(effect) = definition.effect else {
+            return;
+        };
+        if !in_battle && !matches!(effect, ItemEffect::Heal { .. }) {
+            self.add_message(format!("{} si può usare solo in battaglia.", name));
+            return;
+        }
+
+        if in_battle && !self.spend_stamina(definition.stamina_cost, &name) {
+            return;
+        }
 
-        if self.player_health > 100.0 {
-            self.player_health = 100.0
+        if definition.consumable {
+            self.player_inventory.take_one(self.selected_inventory_item);
+            self.clamp_inventory_cursor();
+        }
+        if in_battle {
+            // Using an item takes the player's turn
+            self.previous_game_state = None;
+            self.game_state = GameState::Battle;
+            self.player_action(Action::UseItem(name));
+        } else {
+            self.apply_item_effect(&name, effect);
         }
     }
-    fn logic_story(&mut self) {}
-    fn logic_fight(&mut self) {}
-    fn logic_hook(&mut self) {}
-    fn logic_jab(&mut self) {
-        self.enemy_health = self.enemy_health - self.player_dmg * (self.player_strength / 2.0)
+    // Equipment can only be changed outside of battle; the item leaves the bag
+    // and whatever was worn in its slot goes back in
+    fn logic_equip_item(&mut self) {
+        if self.previous_game_state == Some(GameState::Battle) {
+            self.add_message("Non puoi cambiare equipaggiamento in battaglia.".to_string());
+            return;
+        }
+        let Some(name) = self
+            .player_inventory
+            .get(self.selected_inventory_item)
+            .map(|item| item.name.clone())
+        else {
+            return;
+        };
+        let Some(equipment) = content::item_by_name(&name).and_then(|item| item.equipment) else {
+            self.add_message(format!("{} non si può indossare.", name));
+            return;
+        };
+        self.player_inventory.take_one(self.selected_inventory_item);
+        if let Some(previous) = self.player_equipment.equip(equipment.slot, &name) {
+            self.player_inventory.add(&previous, 1);
+            self.add_message(format!("Togli {}.", previous));
+        }
+        self.clamp_inventory_cursor();
+        self.clamp_health();
+        self.add_message(format!("Indossi {}: {}.", name, equipment.bonus.describe()));
+    }
+    fn logic_unequip(&mut self, slot: EquipSlot) {
+        if self.previous_game_state == Some(GameState::Battle) {
+            self.add_message("Non puoi cambiare equipaggiamento in battaglia.".to_string());
+            return;
+        }
+        let Some(name) = self.player_equipment.unequip(slot) else {
+            self.add_message(format!("Nessun oggetto nello slot {}.", slot.name()));
+            return;
+        };
+        self.player_inventory.add(&name, 1);
+        self.clamp_health();
+        self.add_message(format!("Togli {}.", name));
+    }
+    // Taking off a piece that raised max health can leave the player above it
+    fn clamp_health(&mut self) {
+        self.player_health = self.player_health.min(self.max_health());
+    }
+    fn apply_item_effect(&mut self, name: &str, effect: ItemEffect) {
+        match effect {
+            ItemEffect::Heal { amount } => {
+                let healed = self.heal_player(amount);
+                self.add_message(format!("Usi {}: recuperi {:.0} salute.", name, healed));
+            }
+            ItemEffect::DamageBuff { amount, turns } => {
+                // Using the same item again refreshes the buff instead of stacking it
+                self.player_statuses.apply(StatusEffect::new(
+                    StatusKind::DamageBonus,
+                    name,
+                    amount,
+                    turns,
+                ));
+                self.add_message(format!(
+                    "Usi {}: +{:.0} danni per {} turni.",
+                    name, amount, turns
+                ));
+            }
+            ItemEffect::Damage { amount } => {
+                let amount = amount * self.opponent().defense(None).damage_taken_factor;
+                self.add_message(format!("Usi {}: {:.0} danni al nemico!", name, amount));
+                self.damage_opponent(amount);
+            }
+        }
     }
-    fn logic_montante(&mut self) {}
+    fn logic_heal(&mut self) {
+        let healed = self.heal_player(self.player_heal_value * self.heal_power());
+        if healed > 0.0 {
+            self.add_message(format!("Recuperi {:.0} salute.", healed));
+        } else {
+            self.add_message("Sei già in piena salute.".to_string());
+        }
+    }
+    fn max_health(&self) -> f64 {
+        self.player_max_health + self.player_equipment.bonus().max_health
+    }
+    // Health never goes above the maximum; returns how much was actually restored
+    fn heal_player(&mut self, amount: f64) -> f64 {
+        let before = self.player_health;
+        self.player_health = (self.player_health + amount).min(self.max_health());
+        (self.player_health - before).max(0.0)
+    }
+    fn logic_story(&mut self) {}
     fn logic_minigame(&mut self) {}
+    fn shop_entries(&self) -> &'static [ShopEntry] {
+        content::shop_for_place(self.player_player_place)
+            .map(|shop| shop.items.as_slice())
+            .unwrap_or_default()
+    }
     fn logic_buy(&mut self) {
-        self.player_inventory
-            .push("Pozione della Salute".to_string());
+        let place = self.player_player_place;
+        let Some(entry) = self.shop_entries().get(self.selected_shop_item) else {
+            return;
+        };
+        let price = self.market.buy_price(place, entry);
+        if self.market.stock(place, entry) == 0 {
+            self.add_message(format!("{} è esaurito, torna più tardi.", entry.item));
+            return;
+        }
+        if self.player_gold < price {
+            self.add_message(format!(
+                "Non hai abbastanza oro per {} ({} oro).",
+                entry.item, price
+            ));
+            return;
+        }
+        self.market.take_from_stock(place, &entry.item);
+        self.player_gold -= price;
+        self.player_inventory.add(&entry.item, 1);
+        self.add_message(format!("Hai comprato {} per {} oro.", entry.item, price));
     }
     fn logic_sell(&mut self) {
-        if !self.player_inventory.is_empty() {
-            self.player_inventory.pop(); // Remove the last item as a simple example
+        let place = self.player_player_place;
+        // Sells one unit of the item under the inventory cursor
+        if let Some(item) = self.player_inventory.take_one(self.selected_inventory_item) {
+            self.clamp_inventory_cursor();
+            let price = self.market.sell_price(place, &item.name, item.value);
+            self.market.record_sale(place, &item.name);
+            self.player_gold += price;
+            self.add_message(format!("Hai venduto {} per {} oro.", item.name, price));
+        }
+    }
+    fn clamp_inventory_cursor(&mut self) {
+        self.selected_inventory_item = self
+            .selected_inventory_item
+            .min(self.player_inventory.len().saturating_sub(1));
+    }
+    fn logic_travel(&mut self) {
+        let from = self.player_player_place;
+        let Some(&to) = map::neighbours(from).get(self.selected_destination) else {
+            return;
+        };
+        self.player_player_place = to;
+        self.selected_destination = 0;
+        self.game_state = GameState::Story;
+        self.add_message(format!("Viaggi da {} a {}.", from.name(), to.name()));
+        self.check_for_boss_encounter();
+        if self.game_state != GameState::BossEncounter && self.roll_random_encounter() {
+            self.add_message("Un'imboscata lungo la strada!".to_string());
+            self.start_battle(GameState::Travel);
+        }
+    }
+    fn logic_explore(&mut self) {
+        let place = self.player_player_place.name();
+        if self.roll_random_encounter() {
+            self.start_battle(GameState::Story);
+        } else {
+            self.add_message(format!("Esplori {} senza incontrare nessuno.", place));
         }
     }
+    fn roll_random_encounter(&self) -> bool {
+        let rate = content::encounters().rate(self.player_player_place, self.player_lvl);
+        rand::rng().random_bool(rate)
+    }
     fn logic_game_over(&mut self) {
         self.running = false;
     }
     fn logic_shop(&mut self) {}
+    fn mercy_chance(&self) -> Option<f64> {
+        self.opponent()
+            .mercy_chance(self.player_lvl, self.player_reputation, self.total_wisdom())
+    }
+    // Opponents that refuse any mercy say so before the player can even ask
+    fn open_mercy(&mut self) {
+        if self.dialogue_pending() {
+            return;
+        }
+        self.game_state = GameState::Mercy;
+        self.mercy_outcome = match self.mercy_chance() {
+            Some(_) => None,
+            None => Some(MercyOutcome::Refused),
+        };
+    }
     fn logic_mercy(&mut self) {
-        let secret_number: i32 = rand::thread_rng().gen_range(1..=10);
-        let player_mercy_chance: i32 = 7; // This could be based on player stats or charisma
-        self.mercy_outcome = Some(player_mercy_chance >= secret_number);
+        let Some(chance) = self.mercy_chance() else {
+            self.mercy_outcome = Some(MercyOutcome::Refused);
+            return;
+        };
+        self.mercy_outcome = Some(if rand::rng().random_bool(chance) {
+            MercyOutcome::Accepted
+        } else {
+            MercyOutcome::Rejected
+        });
     }
+    // A rejected offer costs the player their turn
+    fn close_mercy(&mut self, outcome: MercyOutcome) {
+        self.mercy_outcome = None;
+        match outcome {
+            MercyOutcome::Accepted => self.finish_battle(BattleOutcome::Spared),
+            MercyOutcome::Rejected => {
+                self.game_state = GameState::Battle;
+                let opponent_name = self.opponent().name().to_string();
+                self.add_message(format!("{} rifiuta la tua pietà e attacca!", opponent_name));
+                self.turn_queue.push_back(Turn::Opponent);
+                self.run_turns();
+            }
+            MercyOutcome::Refused => self.game_state = GameState::Battle,
+        }
+    }
+    fn logic_spare(&mut self) {
+        self.market.record_battle();
+        if self.is_boss_battle {
+            self.spared_bosses.push(self.current_boss.id.clone());
+            self.player_reputation += battle::SPARE_BOSS_REPUTATION;
+            let Some(mercy) = self
+                .current_boss
+                .definition()
+                .and_then(|definition| definition.mercy.as_ref())
+            else {
+                return;
+            };
+            if let Some(item) = &mercy.reward_item {
+                self.player_inventory.add(item, 1);
+            }
+            self.add_message(format!(
+                "{} +{:.0} XP, +{} reputazione.",
+                mercy.message,
+                self.scaled_xp(mercy.xp_reward),
+                battle::SPARE_BOSS_REPUTATION
+            ));
+            self.gain_xp(mercy.xp_reward);
+        } else {
+            let enemy = self.current_enemy.clone();
+            let xp = enemy.xp_reward * battle::SPARE_XP_FACTOR;
+            self.player_reputation += battle::SPARE_REPUTATION;
+            self.enemies_spared += 1;
+            self.add_message(format!(
+                "Hai risparmiato {}. +{:.0} XP, +{} reputazione.",
+                enemy.name,
+                self.scaled_xp(xp),
+                battle::SPARE_REPUTATION
+            ));
+            self.gain_xp(xp);
+        }
+    }
+    fn logic_save_slot(&mut self) {
+        let slot_name = save::SLOT_NAMES[self.selected_save_slot];
+        match save::save_slot(self.selected_save_slot, &save::SaveData::from_app(self)) {
+            Ok(()) => {
+                self.add_message(format!("Partita salvata in {}.", slot_name));
+                self.game_state = GameState::MainMenu;
+            }
+            Err(err) => self.add_message(format!("Salvataggio fallito: {:#}", err)),
+        }
+    }
+    fn open_save_slots(&mut self, mode: SaveMenuMode) {
+        self.save_entries = save::menu_entries(mode == SaveMenuMode::Load);
+        self.save_summaries = self
+            .save_entries
+            .iter()
+            .map(|(slot_name, path)| match save::load_file(path) {
+                Ok(Some(data)) => format!(
+                    "{} - Livello {:.0}, {}, Capitolo {}",
+                    slot_name,
+                    data.player_lvl,
+                    data.player_player_place.name(),
+                    data.story_state.chapter()
+                ),
+                Ok(None) => format!("{} - vuoto", slot_name),
+                Err(_) => format!("{} - salvataggio danneggiato", slot_name),
+            })
+            .collect();
+        self.save_menu_mode = mode;
+        self.selected_save_slot = 0;
+        self.game_state = GameState::SaveSlots;
+    }
+    fn logic_load_slot(&mut self) {
+        let Some((slot_name, path)) = self.save_entries.get(self.selected_save_slot).cloned()
+        else {
+            return;
+        };
+        match save::load_file(&path) {
+            Ok(Some(data)) => {
+                data.apply_to(self);
+                self.clamp_inventory_cursor();
+                self.selected_fight_option = FightOption::default();
+                self.selected_shop_option = ShopOption::default();
+                self.previous_game_state = None;
+                self.game_state = GameState::Story;
+                self.run_started = true;
+                self.add_message(format!("Partita caricata da {}.", slot_name));
+                // Saves from before the XP curve hold the running total; what is
+                // past the next level is turned into the level-ups it is worth
+                self.gain_xp(0.0);
+            }
+            Ok(None) => self.add_message(format!("{} è vuoto.", slot_name)),
+            Err(err) => self.add_message(format!("Caricamento fallito: {:#}", err)),
+        }
+    }
+    fn autosave(&mut self) {
+        if let Err(err) = save::autosave(&save::SaveData::from_app(self)) {
+            self.add_message(format!("Autosalvataggio fallito: {:#}", err));
+        }
+    }
+    // A run that never started or that just ended in defeat is not worth
+    // keeping, and would push real autosaves out of the history. A battle in
+    // progress is not saved either: loading always returns to the story, so it
+    // would be a way out of boss fights
     fn logic_quit(&mut self) {
+        let run_over = self.game_state == GameState::GameOver || self.player_health <= 0.0;
+        if self.run_started && !run_over && !self.in_battle() {
+            self.autosave();
+        }
         self.running = false;
     }
 }
@@ -0,0 +1,68 @@
+use crate::Places;
+
+// Longitude and latitude bounds of the Mediterranean map
+pub const LON_BOUNDS: [f64; 2] = [10.0, 48.0];
+pub const LAT_BOUNDS: [f64; 2] = [29.0, 44.0];
+
+pub const PLACES: [Places; 11] = [
+    Places::Samos,
+    Places::SabbiaSamos,
+    Places::Tiro,
+    Places::ColonneTiro,
+    Places::Crotone,
+    Places::ScuolaCrotone,
+    Places::Babilonia,
+    Places::BabiloniaBoss,
+    Places::Olimpia,
+    Places::Syros,
+    Places::Mileto,
+];
+
+// Sea and land routes between places, usable in both directions
+pub const ROUTES: [(Places, Places); 12] = [
+    (Places::Samos, Places::SabbiaSamos),
+    (Places::Samos, Places::Mileto),
+    (Places::Samos, Places::Syros),
+    (Places::Mileto, Places::Syros),
+    (Places::Mileto, Places::Tiro),
+    (Places::Tiro, Places::ColonneTiro),
+    (Places::Tiro, Places::Babilonia),
+    (Places::Babilonia, Places::BabiloniaBoss),
+    (Places::Syros, Places::Olimpia),
+    (Places::Olimpia, Places::Crotone),
+    (Places::Crotone, Places::ScuolaCrotone),
+    (Places::Syros, Places::Crotone),
+];
+
+// (longitude, latitude); places that are too close on a real map are spread
+// apart slightly so they stay distinguishable in the terminal
+pub fn coordinates(place: Places) -> (f64, f64) {
+    match place {
+        Places::Samos => (26.8, 37.9),
+        Places::SabbiaSamos => (26.2, 37.2),
+        Places::Tiro => (35.2, 33.3),
+        Places::ColonneTiro => (36.2, 34.4),
+        Places::Crotone => (17.1, 39.1),
+        Places::ScuolaCrotone => (16.2, 40.0),
+        Places::Babilonia => (44.4, 32.5),
+        Places::BabiloniaBoss => (45.4, 31.5),
+        Places::Olimpia => (21.6, 37.6),
+        Places::Syros => (24.9, 37.4),
+        Places::Mileto => (28.0, 37.3),
+    }
+}
+
+pub fn neighbours(place: Places) -> Vec<Places> {
+    ROUTES
+        .iter()
+        .filter_map(|&(from, to)| {
+            if from == place {
+                Some(to)
+            } else if to == place {
+                Some(from)
+            } else {
+                None
+            }
+        })
+        .collect()
+}
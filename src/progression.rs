@@ -0,0 +1,109 @@
+// Experience needed for the first level-up; each level asks for more than the last
+const BASE_XP: f64 = 100.0;
+const XP_GROWTH: f64 = 1.5;
+
+// Stats gained on every level-up
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LevelUpGains {
+    pub max_health: f64,
+    pub damage: f64,
+    pub defense: f64,
+    pub strength: f64,
+    pub heal_value: f64,
+    pub max_stamina: f64,
+}
+
+pub const LEVEL_UP_GAINS: LevelUpGains = LevelUpGains {
+    max_health: 20.0,
+    damage: 2.0,
+    defense: 1.0,
+    strength: 0.5,
+    heal_value: 2.0,
+    max_stamina: 5.0,
+};
+
+impl LevelUpGains {
+    // Appended to the level-up notification, e.g. "HP +20, ATK +2, ..."
+    pub fn describe(&self) -> String {
+        format!(
+            "HP +{:.0}, ATK +{:.0}, DEF +{:.0}, Forza +{:.1}, Cura +{:.0}, Stamina +{:.0}",
+            self.max_health,
+            self.damage,
+            self.defense,
+            self.strength,
+            self.heal_value,
+            self.max_stamina
+        )
+    }
+}
+
+// Every point of wisdom adds this share of XP to each reward
+pub const WISDOM_XP_BONUS: f64 = 0.05;
+
+// XP to collect at `level` before reaching the next one
+pub fn xp_to_next_level(level: f64) -> f64 {
+    (BASE_XP * level.max(1.0).powf(XP_GROWTH)).round()
+}
+
+// Points handed out on every level-up, spent on the stat allocation screen
+pub const STAT_POINTS_PER_LEVEL: u32 = 3;
+
+// Stats the player can raise with their points
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatKind {
+    Strength,
+    Defense,
+    MaxHealth,
+    HealPower,
+    Wisdom,
+}
+
+impl StatKind {
+    pub const ALL: [StatKind; 5] = [
+        StatKind::Strength,
+        StatKind::Defense,
+        StatKind::MaxHealth,
+        StatKind::HealPower,
+        StatKind::Wisdom,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            StatKind::Strength => "Forza",
+            StatKind::Defense => "Difesa",
+            StatKind::MaxHealth => "HP massimi",
+            StatKind::HealPower => "Potere di cura",
+            StatKind::Wisdom => "Saggezza",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            StatKind::Strength => "Ogni colpo fa più male",
+            StatKind::Defense => "Assorbe parte dei colpi nemici",
+            StatKind::MaxHealth => "Più salute da spendere in battaglia",
+            StatKind::HealPower => "Le cure ripristinano più salute",
+            StatKind::Wisdom => "Più probabilità di pietà e più XP da ogni esperienza",
+        }
+    }
+
+    // Heal power is a multiplier and strength grows by halves on level-up
+    pub fn format(&self, value: f64) -> String {
+        match self {
+            StatKind::HealPower => format!("x{:.1}", value),
+            StatKind::Strength => format!("{:.1}", value),
+            _ => format!("{:.0}", value),
+        }
+    }
+
+    // How much a single point raises the stat
+    pub fn per_point(&self) -> f64 {
+        match self {
+            StatKind::Strength => 1.0,
+            StatKind::Defense => 2.0,
+            StatKind::MaxHealth => 10.0,
+            StatKind::HealPower => 0.1,
+            StatKind::Wisdom => 1.0,
+        }
+    }
+}
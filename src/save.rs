@@ -0,0 +1,621 @@
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use color_eyre::{
+    Result,
+    eyre::{WrapErr, bail},
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::inventory::{Equipment, Inventory};
+use crate::shop::Market;
+use crate::{App, Places, StoryState};
+
+pub const SAVE_DIR: &str = "saves";
+pub const SLOT_NAMES: [&str; 3] = ["Slot 1", "Slot 2", "Slot 3"];
+pub const AUTOSAVE_PREFIX: &str = "autosave_";
+pub const AUTOSAVES_KEPT: usize = 5;
+
+// Bump this and append a step to MIGRATIONS whenever SaveData changes shape
+pub const SAVE_VERSION: u32 = 14;
+
+// MIGRATIONS[i] upgrades the data of a version i + 1 save to version i + 2
+const MIGRATIONS: [fn(Value) -> Result<Value>; 13] = [
+    migrate_v1_to_v2,
+    migrate_v2_to_v3,
+    migrate_v3_to_v4,
+    migrate_v4_to_v5,
+    migrate_v5_to_v6,
+    migrate_v6_to_v7,
+    migrate_v7_to_v8,
+    migrate_v8_to_v9,
+    migrate_v9_to_v10,
+    migrate_v10_to_v11,
+    migrate_v11_to_v12,
+    migrate_v12_to_v13,
+    migrate_v13_to_v14,
+];
+
+// On-disk layout: a version header in front of the serialized SaveData
+#[derive(Debug, Serialize, Deserialize)]
+struct SaveFile {
+    version: u32,
+    data: Value,
+}
+
+// Only the persistent part of a run: UI selections and the message log are not saved
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SaveData {
+    pub story_state: StoryState,
+    pub player_strength: f64,
+    pub player_dmg: f64,
+    pub player_health: f64,
+    pub player_max_health: f64,
+    pub player_def: f64,
+    pub player_xp: f64,
+    pub player_lvl: f64,
+    pub player_player_place: Places,
+    pub player_heal_value: f64,
+    pub player_heal_factor: f64,
+    pub player_wisdom: f64,
+    pub stat_points: u32,
+    pub player_xp_factor: f64,
+    pub player_max_stamina: f64,
+    pub player_inventory: Inventory,
+    pub player_equipment: Equipment,
+    pub player_gold: u32,
+    pub market: Market,
+    pub defeated_bosses: Vec<String>,
+    pub spared_bosses: Vec<String>,
+    pub enemies_spared: u32,
+    pub enemies_defeated: u32,
+    pub player_reputation: i32,
+}
+
+impl SaveData {
+    pub fn from_app(app: &App) -> Self {
+        Self {
+            story_state: app.story_state,
+            player_strength: app.player_strength,
+            player_dmg: app.player_dmg,
+            player_health: app.player_health,
+            player_max_health: app.player_max_health,
+            player_def: app.player_def,
+            player_xp: app.player_xp,
+            player_lvl: app.player_lvl,
+            player_player_place: app.player_player_place,
+            player_heal_value: app.player_heal_value,
+            player_heal_factor: app.player_heal_factor,
+            player_wisdom: app.player_wisdom,
+            stat_points: app.stat_points,
+            player_xp_factor: app.player_xp_factor,
+            player_max_stamina: app.player_max_stamina,
+            player_inventory: app.player_inventory.clone(),
+            player_equipment: app.player_equipment.clone(),
+            player_gold: app.player_gold,
+            market: app.market.clone(),
+            defeated_bosses: app.defeated_bosses.clone(),
+            spared_bosses: app.spared_bosses.clone(),
+            enemies_spared: app.enemies_spared,
+            enemies_defeated: app.enemies_defeated,
+            player_reputation: app.player_reputation,
+        }
+    }
+
+    pub fn apply_to(self, app: &mut App) {
+        app.story_state = self.story_state;
+        app.player_strength = self.player_strength;
+        app.player_dmg = self.player_dmg;
+        app.player_health = self.player_health;
+        app.player_max_health = self.player_max_health;
+        app.player_def = self.player_def;
+        app.player_xp = self.player_xp;
+        app.player_lvl = self.player_lvl;
+        app.player_player_place = self.player_player_place;
+        app.player_heal_value = self.player_heal_value;
+        app.player_heal_factor = self.player_heal_factor;
+        app.player_wisdom = self.player_wisdom;
+        app.stat_points = self.stat_points;
+        app.player_xp_factor = self.player_xp_factor;
+        app.player_max_stamina = self.player_max_stamina;
+        app.player_stamina = self.player_max_stamina;
+        app.player_inventory = self.player_inventory;
+        app.player_equipment = self.player_equipment;
+        app.player_gold = self.player_gold;
+        app.market = self.market;
+        app.defeated_bosses = self.defeated_bosses;
+        app.spared_bosses = self.spared_bosses;
+        app.enemies_spared = self.enemies_spared;
+        app.enemies_defeated = self.enemies_defeated;
+        app.player_reputation = self.player_reputation;
+    }
+}
+
+pub fn slot_path(slot: usize) -> PathBuf {
+    PathBuf::from(SAVE_DIR).join(format!("slot_{}.json", slot + 1))
+}
+
+pub fn save_slot(slot: usize, data: &SaveData) -> Result<()> {
+    write_save(&slot_path(slot), data)
+}
+
+// Returns None when the slot has never been written
+pub fn load_file(path: &Path) -> Result<Option<SaveData>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let json = fs::read_to_string(path)
+        .wrap_err_with(|| format!("impossibile leggere {}", path.display()))?;
+    let data = parse_save(&json)
+        .wrap_err_with(|| format!("salvataggio non valido: {}", path.display()))?;
+    Ok(Some(data))
+}
+
+fn parse_save(json: &str) -> Result<SaveData> {
+    let raw: Value = serde_json::from_str(json)?;
+    // Saves written before the header existed are the bare SaveData object
+    let (version, mut data) = if raw.get("version").is_some() && raw.get("data").is_some() {
+        let file: SaveFile = serde_json::from_value(raw)?;
+        (file.version, file.data)
+    } else {
+        (1, raw)
+    };
+
+    if version == 0 {
+        bail!("versione del salvataggio non valida: 0");
+    }
+    if version > SAVE_VERSION {
+        bail!(
+            "il salvataggio usa la versione {} ma questo gioco supporta fino alla versione {}: aggiorna il gioco",
+            version,
+            SAVE_VERSION
+        );
+    }
+
+    for (step, migration) in MIGRATIONS.iter().enumerate().skip(version as usize - 1) {
+        data = migration(data)
+            .wrap_err_with(|| format!("migrazione dalla versione {} fallita", step + 1))?;
+    }
+    Ok(serde_json::from_value(data)?)
+}
+
+// Version 2 only introduced the header; the data itself is unchanged
+fn migrate_v1_to_v2(data: Value) -> Result<Value> {
+    Ok(data)
+}
+
+// Version 3 identifies bosses by their content id instead of only by BossType
+fn migrate_v2_to_v3(mut data: Value) -> Result<Value> {
+    let Some(boss) = data.get_mut("current_boss").and_then(Value::as_object_mut) else {
+        bail!("current_boss mancante");
+    };
+    let id = match boss.get("boss_type").and_then(Value::as_str) {
+        Some("SamosGuardian") => "samos_guardian",
+        Some("TyrantOfTyre") => "tyrant_of_tyre",
+        Some("BabylonianSage") => "babylonian_sage",
+        Some("FinalBoss") => "final_boss",
+        _ => "",
+    };
+    boss.insert("id".to_string(), Value::from(id));
+    Ok(data)
+}
+
+// Version 4 stacks the inventory into items with quantities
+fn migrate_v3_to_v4(mut data: Value) -> Result<Value> {
+    let Some(names) = data.get("player_inventory").and_then(Value::as_array) else {
+        bail!("player_inventory mancante");
+    };
+    let mut inventory = Inventory::default();
+    for name in names {
+        let Some(name) = name.as_str() else {
+            bail!("oggetto non valido nell'inventario: {}", name);
+        };
+        inventory.add(name, 1);
+    }
+    data["player_inventory"] = serde_json::to_value(inventory)?;
+    Ok(data)
+}
+
+// Version 5 adds gold; older runs start with the same purse as a new game
+fn migrate_v4_to_v5(mut data: Value) -> Result<Value> {
+    let Some(fields) = data.as_object_mut() else {
+        bail!("dati del salvataggio non validi");
+    };
+    fields.insert("player_gold".to_string(), Value::from(50));
+    Ok(data)
+}
+
+// Version 6 tracks shop stock; every shop starts fully stocked again
+fn migrate_v5_to_v6(mut data: Value) -> Result<Value> {
+    let Some(fields) = data.as_object_mut() else {
+        bail!("dati del salvataggio non validi");
+    };
+    fields.insert("market".to_string(), Value::Object(Default::default()));
+    Ok(data)
+}
+
+// Version 7 remembers defeated bosses; older saves only know about the current one
+fn migrate_v6_to_v7(mut data: Value) -> Result<Value> {
+    let Some(fields) = data.as_object_mut() else {
+        bail!("dati del salvataggio non validi");
+    };
+    let mut defeated_bosses = vec![];
+    if let Some(boss) = fields.get("current_boss") {
+        let id = boss.get("id").and_then(Value::as_str).unwrap_or_default();
+        let defeated = boss.get("defeated").and_then(Value::as_bool) == Some(true);
+        if defeated && !id.is_empty() {
+            defeated_bosses.push(Value::from(id));
+        }
+    }
+    fields.insert("defeated_bosses".to_string(), Value::Array(defeated_bosses));
+    Ok(data)
+}
+
+// Version 8 adds stamina; older runs get the same pool as a new game
+fn migrate_v7_to_v8(mut data: Value) -> Result<Value> {
+    let Some(fields) = data.as_object_mut() else {
+        bail!("dati del salvataggio non validi");
+    };
+    fields.insert("player_max_stamina".to_string(), Value::from(50.0));
+    Ok(data)
+}
+
+// Version 9 adds mercy: nobody has been spared yet and reputation starts neutral
+fn migrate_v8_to_v9(mut data: Value) -> Result<Value> {
+    let Some(fields) = data.as_object_mut() else {
+        bail!("dati del salvataggio non validi");
+    };
+    fields.insert("spared_bosses".to_string(), Value::Array(vec![]));
+    fields.insert("player_reputation".to_string(), Value::from(0));
+    Ok(data)
+}
+
+// Version 10 counts regular enemies spared and defeated; earlier fights are not known
+fn migrate_v9_to_v10(mut data: Value) -> Result<Value> {
+    let Some(fields) = data.as_object_mut() else {
+        bail!("dati del salvataggio non validi");
+    };
+    fields.insert("enemies_spared".to_string(), Value::from(0));
+    fields.insert("enemies_defeated".to_string(), Value::from(0));
+    Ok(data)
+}
+
+// Version 11 makes max health a stat; older runs get what the stats panel showed
+// for their level, and health above it is capped
+fn migrate_v10_to_v11(mut data: Value) -> Result<Value> {
+    let Some(fields) = data.as_object_mut() else {
+        bail!("dati del salvataggio non validi");
+    };
+    let level = fields
+        .get("player_lvl")
+        .and_then(Value::as_f64)
+        .unwrap_or(1.0);
+    let max_health = 100.0 + (level - 1.0) * 20.0;
+    if let Some(health) = fields.get("player_health").and_then(Value::as_f64) {
+        fields.insert(
+            "player_health".to_string(),
+            Value::from(health.min(max_health)),
+        );
+    }
+    fields.insert("player_max_health".to_string(), Value::from(max_health));
+    Ok(data)
+}
+
+// Version 12 adds wisdom and unspent stat points; levels gained before do not award any
+fn migrate_v11_to_v12(mut data: Value) -> Result<Value> {
+    let Some(fields) = data.as_object_mut() else {
+        bail!("dati del salvataggio non validi");
+    };
+    fields.insert("player_wisdom".to_string(), Value::from(0.0));
+    fields.insert("stat_points".to_string(), Value::from(0));
+    Ok(data)
+}
+
+// Version 13 adds equipment slots. The Tyrant and the Sage used to raise damage
+// and healing directly; those bonuses now come from wearing their trophies, so a
+// trophy still in the bag is moved into the empty relic slot and its bonus taken
+// back out of the stats. Only one relic fits, and a trophy that was sold keeps
+// its old bonus
+fn migrate_v12_to_v13(mut data: Value) -> Result<Value> {
+    let Some(fields) = data.as_object_mut() else {
+        bail!("dati del salvataggio non validi");
+    };
+    let Some(inventory) = fields.get("player_inventory") else {
+        bail!("player_inventory mancante");
+    };
+    let mut inventory: Inventory = serde_json::from_value(inventory.clone())?;
+    let defeated = |id: &str| {
+        fields
+            .get("defeated_bosses")
+            .and_then(Value::as_array)
+            .is_some_and(|bosses| bosses.iter().any(|boss| boss.as_str() == Some(id)))
+    };
+    let trophies = [
+        ("tyrant_of_tyre", "Corona del Tiranno", "player_dmg", 5.0),
+        (
+            "babylonian_sage",
+            "Tavoletta Babilonese Antica",
+            "player_heal_factor",
+            0.5,
+        ),
+    ]
+    .into_iter()
+    .filter(|(boss, _, _, _)| defeated(boss))
+    .collect::<Vec<_>>();
+
+    let mut equipment = Equipment::default();
+    for (_, trophy, stat, bonus) in trophies {
+        if equipment.relic.is_some() {
+            break;
+        }
+        let Some(index) = inventory.items.iter().position(|item| item.name == trophy) else {
+            continue;
+        };
+        let Some(value) = fields.get(stat).and_then(Value::as_f64) else {
+            bail!("{} mancante", stat);
+        };
+        inventory.take_one(index);
+        equipment.relic = Some(trophy.to_string());
+        fields.insert(stat.to_string(), Value::from(value - bonus));
+    }
+    fields.insert(
+        "player_inventory".to_string(),
+        serde_json::to_value(inventory)?,
+    );
+    fields.insert(
+        "player_equipment".to_string(),
+        serde_json::to_value(equipment)?,
+    );
+    Ok(data)
+}
+
+// Version 14 stops saving the boss of the last battle, which only matters while
+// a battle is in progress and runs are never saved during one
+fn migrate_v13_to_v14(mut data: Value) -> Result<Value> {
+    let Some(fields) = data.as_object_mut() else {
+        bail!("dati del salvataggio non validi");
+    };
+    fields.remove("is_boss_battle");
+    fields.remove("current_boss");
+    Ok(data)
+}
+
+// Writes a new autosave and drops the oldest ones beyond AUTOSAVES_KEPT
+pub fn autosave(data: &SaveData) -> Result<()> {
+    let millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis())
+        .unwrap_or_default();
+    let path = PathBuf::from(SAVE_DIR).join(format!("{}{:013}.json", AUTOSAVE_PREFIX, millis));
+    write_save(&path, data)?;
+
+    for old in autosave_paths()?.into_iter().skip(AUTOSAVES_KEPT) {
+        fs::remove_file(&old)
+            .wrap_err_with(|| format!("impossibile rimuovere {}", old.display()))?;
+    }
+    Ok(())
+}
+
+// Autosaves sorted from the most recent to the oldest
+pub fn autosave_paths() -> Result<Vec<PathBuf>> {
+    if !Path::new(SAVE_DIR).exists() {
+        return Ok(vec![]);
+    }
+    let mut paths: Vec<PathBuf> = fs::read_dir(SAVE_DIR)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.extension().is_some_and(|ext| ext == "json")
+                && path
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| name.starts_with(AUTOSAVE_PREFIX))
+        })
+        .collect();
+    paths.sort();
+    paths.reverse();
+    Ok(paths)
+}
+
+// Manual slots first, then (when loading) the autosaves from newest to oldest
+pub fn menu_entries(include_autosaves: bool) -> Vec<(String, PathBuf)> {
+    let mut entries: Vec<(String, PathBuf)> = SLOT_NAMES
+        .iter()
+        .enumerate()
+        .map(|(i, name)| (name.to_string(), slot_path(i)))
+        .collect();
+    if include_autosaves {
+        let autosaves = autosave_paths().unwrap_or_default();
+        for (i, path) in autosaves.into_iter().enumerate() {
+            entries.push((format!("Autosalvataggio {}", i + 1), path));
+        }
+    }
+    entries
+}
+
+// The save is written to a temporary file and renamed over the target, so an
+// interrupted write never leaves a truncated save behind
+fn write_save(path: &Path, data: &SaveData) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).wrap_err("impossibile creare la cartella dei salvataggi")?;
+    }
+    let file = SaveFile {
+        version: SAVE_VERSION,
+        data: serde_json::to_value(data)?,
+    };
+    let json = serde_json::to_string_pretty(&file)?;
+    let tmp_path = path.with_extension("json.tmp");
+    {
+        let mut file = File::create(&tmp_path)
+            .wrap_err_with(|| format!("impossibile creare {}", tmp_path.display()))?;
+        file.write_all(json.as_bytes())?;
+        file.sync_all()?;
+    }
+    fs::rename(&tmp_path, path).wrap_err_with(|| format!("impossibile scrivere {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    // A save as written before the version header existed
+    fn v1_save() -> Value {
+        json!({
+            "story_state": "Second",
+            "player_strength": 2.0,
+            "player_dmg": 20.0,
+            "player_health": 500.0,
+            "player_def": 5.0,
+            "player_xp": 30.0,
+            "player_lvl": 3.0,
+            "player_player_place": "Tiro",
+            "player_heal_value": 10.0,
+            "player_heal_factor": 1.5,
+            "player_xp_factor": 1.0,
+            "player_inventory": [
+                "Pozione della Salute",
+                "Pozione della Salute",
+                "Pane d'Orzo",
+                "Corona del Tiranno"
+            ],
+            "is_boss_battle": false,
+            "current_boss": {
+                "boss_type": "TyrantOfTyre",
+                "name": "Tiranno di Tiro",
+                "max_health": 200.0,
+                "current_health": 0.0,
+                "damage": 15.0,
+                "defense": 5.0,
+                "special_attack_cooldown": 3,
+                "current_cooldown": 0,
+                "phase": 2,
+                "defeated": true,
+                "description": "",
+                "special_ability": ""
+            }
+        })
+    }
+
+    #[test]
+    fn headerless_v1_save_is_migrated_to_the_current_version() {
+        let data = parse_save(&v1_save().to_string()).unwrap();
+
+        assert_eq!(data.defeated_bosses, vec!["tyrant_of_tyre".to_string()]);
+        let potions = data.player_inventory.get(0).unwrap();
+        assert_eq!(potions.name, "Pozione della Salute");
+        assert_eq!(potions.quantity, 2);
+        assert_eq!(data.player_gold, 50);
+        assert_eq!(data.player_max_stamina, 50.0);
+        assert!(data.spared_bosses.is_empty());
+        assert_eq!(data.player_reputation, 0);
+        assert_eq!(data.enemies_spared, 0);
+        assert_eq!(data.enemies_defeated, 0);
+        // Level 3 showed 140 max health; the extra health is capped
+        assert_eq!(data.player_max_health, 140.0);
+        assert_eq!(data.player_health, 140.0);
+        assert_eq!(data.player_wisdom, 0.0);
+        assert_eq!(data.stat_points, 0);
+        // The Corona moves from the bag to the relic slot and brings its bonus along
+        assert_eq!(
+            data.player_equipment.relic.as_deref(),
+            Some("Corona del Tiranno")
+        );
+        assert_eq!(data.player_inventory.len(), 2);
+        assert_eq!(data.player_dmg, 15.0);
+        assert_eq!(data.player_heal_factor, 1.5);
+    }
+
+    // The fields migrate_v12_to_v13 looks at, with both trophy bosses defeated
+    fn v12_save(inventory: &[&str]) -> Value {
+        let mut bag = Inventory::default();
+        for name in inventory {
+            bag.add(name, 1);
+        }
+        json!({
+            "player_dmg": 20.0,
+            "player_heal_factor": 1.5,
+            "player_inventory": bag,
+            "defeated_bosses": ["tyrant_of_tyre", "babylonian_sage"]
+        })
+    }
+
+    #[test]
+    fn sold_trophies_keep_their_old_bonus() {
+        let data = migrate_v12_to_v13(v12_save(&["Pane d'Orzo"])).unwrap();
+        assert_eq!(data["player_dmg"], 20.0);
+        assert_eq!(data["player_heal_factor"], 1.5);
+        assert_eq!(data["player_equipment"]["relic"], Value::Null);
+        assert_eq!(data["player_inventory"][0]["name"], "Pane d'Orzo");
+    }
+
+    #[test]
+    fn only_one_trophy_takes_the_relic_slot() {
+        let inventory = ["Tavoletta Babilonese Antica", "Corona del Tiranno"];
+        let data = migrate_v12_to_v13(v12_save(&inventory)).unwrap();
+        assert_eq!(data["player_equipment"]["relic"], "Corona del Tiranno");
+        assert_eq!(data["player_dmg"], 15.0);
+        // The Tavoletta stays in the bag, so the Sage's bonus is left in place
+        assert_eq!(data["player_heal_factor"], 1.5);
+        let bag = data["player_inventory"].as_array().unwrap();
+        assert_eq!(bag.len(), 1);
+        assert_eq!(bag[0]["name"], "Tavoletta Babilonese Antica");
+    }
+
+    #[test]
+    fn the_sage_trophy_is_worn_when_the_crown_is_gone() {
+        let data = migrate_v12_to_v13(v12_save(&["Tavoletta Babilonese Antica"])).unwrap();
+        assert_eq!(
+            data["player_equipment"]["relic"],
+            "Tavoletta Babilonese Antica"
+        );
+        assert_eq!(data["player_dmg"], 20.0);
+        assert_eq!(data["player_heal_factor"], 1.0);
+        assert!(data["player_inventory"].as_array().unwrap().is_empty());
+    }
+
+    #[test]
+    fn saves_from_a_newer_game_are_rejected() {
+        let json = json!({ "version": SAVE_VERSION + 1, "data": {} }).to_string();
+        let err = parse_save(&json).unwrap_err();
+        assert!(err.to_string().contains("aggiorna il gioco"), "{err}");
+    }
+
+    #[test]
+    fn version_zero_is_rejected() {
+        let json = json!({ "version": 0, "data": v1_save() }).to_string();
+        let err = parse_save(&json).unwrap_err();
+        assert!(err.to_string().contains("non valida"), "{err}");
+    }
+
+    #[test]
+    fn current_saves_survive_a_round_trip() {
+        let mut app = App::new();
+        app.player_lvl = 4.0;
+        app.player_gold = 123;
+        app.player_player_place = Places::Crotone;
+        app.player_inventory.add("Pane d'Orzo", 3);
+        app.defeated_bosses.push("samos_guardian".to_string());
+        let data = SaveData::from_app(&app);
+
+        let dir = std::env::temp_dir().join(format!("pythagora_save_test_{}", std::process::id()));
+        let path = dir.join("slot.json");
+        write_save(&path, &data).unwrap();
+        let loaded = load_file(&path);
+        fs::remove_dir_all(&dir).unwrap();
+
+        let loaded = loaded.unwrap().expect("the save was just written");
+        assert_eq!(
+            serde_json::to_value(&loaded).unwrap(),
+            serde_json::to_value(&data).unwrap()
+        );
+    }
+
+    #[test]
+    fn missing_files_are_empty_slots() {
+        let path = std::env::temp_dir().join("pythagora_save_test_missing.json");
+        assert!(load_file(&path).unwrap().is_none());
+    }
+}
@@ -0,0 +1,142 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::Places;
+use crate::content::{self, ShopDefinition, ShopEntry};
+
+// Price factor for goods traded in the place they come from
+const ORIGIN_DISCOUNT: f64 = 0.75;
+// Every unit sold in a shop lowers what it pays and charges for that item
+const SOLD_SELL_PENALTY: f64 = 0.1;
+const SOLD_BUY_DISCOUNT: f64 = 0.05;
+const MIN_SELL_FACTOR: f64 = 0.4;
+const MIN_BUY_FACTOR: f64 = 0.7;
+
+// Stock and trading history of one shop; created when the shop is first used
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ShopState {
+    pub stock: HashMap<String, u32>,
+    pub sold: HashMap<String, u32>,
+    pub battles_since_restock: u32,
+}
+
+impl ShopState {
+    fn stocked(shop: &ShopDefinition) -> Self {
+        let mut state = ShopState::default();
+        state.restock(shop);
+        state
+    }
+
+    // Refills the shelves and lets the market forget half of what was sold
+    fn restock(&mut self, shop: &ShopDefinition) {
+        for entry in &shop.items {
+            let stock = self.stock.entry(entry.item.clone()).or_default();
+            *stock = (*stock).max(entry.stock);
+        }
+        for sold in self.sold.values_mut() {
+            *sold /= 2;
+        }
+        self.sold.retain(|_, sold| *sold > 0);
+        self.battles_since_restock = 0;
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Market {
+    pub shops: HashMap<Places, ShopState>,
+}
+
+impl Market {
+    pub fn stock(&self, place: Places, entry: &ShopEntry) -> u32 {
+        match self.shops.get(&place) {
+            Some(state) => state.stock.get(&entry.item).copied().unwrap_or_default(),
+            None => entry.stock,
+        }
+    }
+
+    pub fn sold(&self, place: Places, item: &str) -> u32 {
+        self.shops
+            .get(&place)
+            .and_then(|state| state.sold.get(item))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    pub fn buy_price(&self, place: Places, entry: &ShopEntry) -> u32 {
+        let sold = self.sold(place, &entry.item) as f64;
+        let factor = origin_factor(&entry.item, place)
+            * (1.0 - SOLD_BUY_DISCOUNT * sold).max(MIN_BUY_FACTOR);
+        scale(entry.price, factor)
+    }
+
+    pub fn sell_price(&self, place: Places, item: &str, value: u32) -> u32 {
+        let sold = self.sold(place, item) as f64;
+        let factor =
+            origin_factor(item, place) * (1.0 - SOLD_SELL_PENALTY * sold).max(MIN_SELL_FACTOR);
+        scale(value, factor)
+    }
+
+    // Returns false when the shop has run out of the item
+    pub fn take_from_stock(&mut self, place: Places, item: &str) -> bool {
+        let Some(state) = self.shop_mut(place) else {
+            return false;
+        };
+        match state.stock.get_mut(item) {
+            Some(stock) if *stock > 0 => {
+                *stock -= 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    // Items the shop deals in go back on its shelves
+    pub fn record_sale(&mut self, place: Places, item: &str) {
+        let Some(state) = self.shop_mut(place) else {
+            return;
+        };
+        *state.sold.entry(item.to_string()).or_default() += 1;
+        if let Some(stock) = state.stock.get_mut(item) {
+            *stock += 1;
+        }
+    }
+
+    // Each shop restocks after its own number of battles
+    pub fn record_battle(&mut self) {
+        for (place, state) in self.shops.iter_mut() {
+            let Some(shop) = content::shop_for_place(*place) else {
+                continue;
+            };
+            state.battles_since_restock += 1;
+            if state.battles_since_restock >= shop.restock_after_battles {
+                state.restock(shop);
+            }
+        }
+    }
+
+    fn shop_mut(&mut self, place: Places) -> Option<&mut ShopState> {
+        let shop = content::shop_for_place(place)?;
+        Some(
+            self.shops
+                .entry(place)
+                .or_insert_with(|| ShopState::stocked(shop)),
+        )
+    }
+}
+
+fn origin_factor(item: &str, place: Places) -> f64 {
+    match content::item_by_name(item) {
+        Some(definition) if definition.origin == Some(place) => ORIGIN_DISCOUNT,
+        _ => 1.0,
+    }
+}
+
+// Adjusted prices never drop to zero for something that is worth anything
+fn scale(price: u32, factor: f64) -> u32 {
+    if price == 0 {
+        return 0;
+    }
+    ((price as f64 * factor).round() as u32).max(1)
+}
@@ -0,0 +1,299 @@
+use serde::{Deserialize, Serialize};
+
+// Temporary effects on the player or a boss. Every effect lasts a number of
+// the holder's turns and is removed once they run out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StatusKind {
+    Shield,      // Cuts the damage taken by `magnitude` (0.5 = halved)
+    Weakness,    // Lowers the damage dealt by `magnitude`
+    Empowered,   // Raises the damage dealt by `magnitude`
+    DamageBonus, // Adds `magnitude` flat damage, granted by items
+}
+
+// How a new effect combines with one of the same kind that is still active
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StackRule {
+    // An effect from the same source is replaced; different sources add up
+    Refresh,
+    // Magnitudes add up to `max` and the duration is reset
+    Stack { max: f64 },
+}
+
+impl StatusKind {
+    pub fn stack_rule(&self) -> StackRule {
+        match self {
+            StatusKind::Shield => StackRule::Refresh,
+            StatusKind::Weakness => StackRule::Stack { max: 0.3 },
+            StatusKind::Empowered => StackRule::Stack { max: 0.3 },
+            StatusKind::DamageBonus => StackRule::Refresh,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            StatusKind::Shield => "Scudo",
+            StatusKind::Weakness => "Debolezza",
+            StatusKind::Empowered => "Potenziamento",
+            StatusKind::DamageBonus => "Bonus danni",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatusEffect {
+    pub kind: StatusKind,
+    pub source: String,
+    pub magnitude: f64,
+    pub turns_left: u32,
+}
+
+impl StatusEffect {
+    pub fn new(kind: StatusKind, source: &str, magnitude: f64, turns: u32) -> Self {
+        Self {
+            kind,
+            source: source.to_string(),
+            magnitude,
+            turns_left: turns,
+        }
+    }
+
+    // Short description for the stats panels, e.g. "Scudo 50% (2t)"
+    pub fn label(&self) -> String {
+        let percent = self.magnitude * 100.0;
+        match self.kind {
+            StatusKind::DamageBonus => {
+                format!(
+                    "{} +{:.0} ({}t)",
+                    self.source, self.magnitude, self.turns_left
+                )
+            }
+            StatusKind::Shield => format!(
+                "{} {:.0}% ({}t)",
+                self.kind.name(),
+                percent,
+                self.turns_left
+            ),
+            StatusKind::Weakness => format!(
+                "{} -{:.0}% ({}t)",
+                self.kind.name(),
+                percent,
+                self.turns_left
+            ),
+            StatusKind::Empowered => format!(
+                "{} +{:.0}% ({}t)",
+                self.kind.name(),
+                percent,
+                self.turns_left
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StatusEffects {
+    pub effects: Vec<StatusEffect>,
+}
+
+impl StatusEffects {
+    pub fn apply(&mut self, effect: StatusEffect) {
+        match effect.kind.stack_rule() {
+            StackRule::Refresh => {
+                self.effects
+                    .retain(|active| active.kind != effect.kind || active.source != effect.source);
+                self.effects.push(effect);
+            }
+            StackRule::Stack { max } => {
+                match self
+                    .effects
+                    .iter_mut()
+                    .find(|active| active.kind == effect.kind)
+                {
+                    Some(active) => {
+                        active.magnitude = (active.magnitude + effect.magnitude).min(max);
+                        active.turns_left = active.turns_left.max(effect.turns_left);
+                    }
+                    None => self.effects.push(StatusEffect {
+                        magnitude: effect.magnitude.min(max),
+                        ..effect
+                    }),
+                }
+            }
+        }
+    }
+
+    // Counts down one turn of the holder and returns the effects that expired
+    pub fn tick(&mut self) -> Vec<StatusEffect> {
+        for effect in &mut self.effects {
+            effect.turns_left = effect.turns_left.saturating_sub(1);
+        }
+        let (expired, active) = self
+            .effects
+            .drain(..)
+            .partition(|effect| effect.turns_left == 0);
+        self.effects = active;
+        expired
+    }
+
+    pub fn clear(&mut self) {
+        self.effects.clear();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.effects.is_empty()
+    }
+
+    fn total(&self, kind: StatusKind) -> f64 {
+        self.effects
+            .iter()
+            .filter(|effect| effect.kind == kind)
+            .map(|effect| effect.magnitude)
+            .sum()
+    }
+
+    pub fn damage_bonus(&self) -> f64 {
+        self.total(StatusKind::DamageBonus)
+    }
+
+    // Multiplier for the damage the holder deals
+    pub fn damage_dealt_factor(&self) -> f64 {
+        (1.0 + self.total(StatusKind::Empowered) - self.total(StatusKind::Weakness)).max(0.0)
+    }
+
+    // Multiplier for the damage the holder takes; shields do not add up
+    pub fn damage_taken_factor(&self) -> f64 {
+        let shield = self
+            .effects
+            .iter()
+            .filter(|effect| effect.kind == StatusKind::Shield)
+            .map(|effect| effect.magnitude)
+            .fold(0.0, f64::max);
+        1.0 - shield.min(1.0)
+    }
+
+    pub fn labels(&self) -> Vec<String> {
+        self.effects.iter().map(StatusEffect::label).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-9,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    #[test]
+    fn stacking_effects_add_up_to_their_cap() {
+        let mut statuses = StatusEffects::default();
+        for _ in 0..5 {
+            statuses.apply(StatusEffect::new(StatusKind::Weakness, "Caos", 0.1, 3));
+        }
+        assert_eq!(statuses.effects.len(), 1);
+        assert_close(statuses.effects[0].magnitude, 0.3);
+        assert_close(statuses.damage_dealt_factor(), 0.7);
+    }
+
+    #[test]
+    fn a_single_stacking_effect_is_capped_too() {
+        let mut statuses = StatusEffects::default();
+        statuses.apply(StatusEffect::new(StatusKind::Empowered, "Teorema", 0.8, 3));
+        assert_close(statuses.effects[0].magnitude, 0.3);
+    }
+
+    #[test]
+    fn stacking_resets_to_the_longest_duration() {
+        let mut statuses = StatusEffects::default();
+        statuses.apply(StatusEffect::new(StatusKind::Empowered, "Teorema", 0.1, 3));
+        statuses.tick();
+        statuses.apply(StatusEffect::new(StatusKind::Empowered, "Teorema", 0.1, 3));
+        assert_eq!(statuses.effects[0].turns_left, 3);
+    }
+
+    #[test]
+    fn refresh_replaces_an_effect_from_the_same_source() {
+        let mut statuses = StatusEffects::default();
+        statuses.apply(StatusEffect::new(
+            StatusKind::DamageBonus,
+            "Incenso",
+            6.0,
+            4,
+        ));
+        statuses.apply(StatusEffect::new(
+            StatusKind::DamageBonus,
+            "Incenso",
+            6.0,
+            2,
+        ));
+        assert_eq!(statuses.effects.len(), 1);
+        assert_eq!(statuses.effects[0].turns_left, 2);
+        assert_close(statuses.damage_bonus(), 6.0);
+    }
+
+    #[test]
+    fn refresh_keeps_effects_from_different_sources() {
+        let mut statuses = StatusEffects::default();
+        statuses.apply(StatusEffect::new(
+            StatusKind::DamageBonus,
+            "Incenso",
+            6.0,
+            4,
+        ));
+        statuses.apply(StatusEffect::new(
+            StatusKind::DamageBonus,
+            "Unguento",
+            8.0,
+            3,
+        ));
+        assert_eq!(statuses.effects.len(), 2);
+        assert_close(statuses.damage_bonus(), 14.0);
+    }
+
+    #[test]
+    fn shields_take_the_largest_instead_of_adding_up() {
+        let mut statuses = StatusEffects::default();
+        statuses.apply(StatusEffect::new(
+            StatusKind::Shield,
+            "Scudo Geometrico",
+            0.5,
+            2,
+        ));
+        statuses.apply(StatusEffect::new(StatusKind::Shield, "Egida", 0.3, 2));
+        assert_eq!(statuses.effects.len(), 2);
+        assert_close(statuses.damage_taken_factor(), 0.5);
+    }
+
+    #[test]
+    fn tick_returns_the_expired_effects() {
+        let mut statuses = StatusEffects::default();
+        statuses.apply(StatusEffect::new(
+            StatusKind::Shield,
+            "Scudo Geometrico",
+            0.5,
+            1,
+        ));
+        statuses.apply(StatusEffect::new(StatusKind::Weakness, "Caos", 0.1, 2));
+
+        let expired = statuses.tick();
+        assert_eq!(expired.len(), 1);
+        assert_eq!(expired[0].kind, StatusKind::Shield);
+        assert_eq!(statuses.effects.len(), 1);
+        assert_eq!(statuses.effects[0].turns_left, 1);
+
+        let expired = statuses.tick();
+        assert_eq!(expired[0].kind, StatusKind::Weakness);
+        assert!(statuses.is_empty());
+        assert!(statuses.tick().is_empty());
+    }
+
+    #[test]
+    fn no_effects_leave_damage_unchanged() {
+        let statuses = StatusEffects::default();
+        assert_close(statuses.damage_dealt_factor(), 1.0);
+        assert_close(statuses.damage_taken_factor(), 1.0);
+        assert_close(statuses.damage_bonus(), 0.0);
+    }
+}